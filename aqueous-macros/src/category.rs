@@ -0,0 +1,108 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    token::Comma,
+    Attribute, Error, Ident, LitStr, Result, Visibility,
+};
+
+/// `category!(pub Account, "account")`
+pub struct CategoryDeclaration {
+    attrs: Vec<Attribute>,
+    visibility: Visibility,
+    ident: Ident,
+    category: LitStr,
+}
+
+impl Parse for CategoryDeclaration {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let visibility = input.parse()?;
+        let ident = input.parse()?;
+        input.parse::<Comma>()?;
+        let category: LitStr = input.parse()?;
+        let _ = input.parse::<Comma>();
+
+        let value = category.value();
+        if value.is_empty() || value.contains(['-', ':', '+']) {
+            let message = "category must be non-empty and cannot contain separators";
+            return Err(Error::new(category.span(), message));
+        }
+
+        // The characters `StreamName::parse` rejects anywhere in a stream name
+        if value
+            .chars()
+            .any(|char| char.is_whitespace() || char.is_control())
+        {
+            let message = "category cannot contain whitespace or control characters";
+            return Err(Error::new(category.span(), message));
+        }
+
+        Ok(Self {
+            attrs,
+            visibility,
+            ident,
+            category,
+        })
+    }
+}
+
+pub fn expand(declaration: CategoryDeclaration) -> TokenStream {
+    let CategoryDeclaration {
+        attrs,
+        visibility,
+        ident,
+        category,
+    } = declaration;
+
+    quote! {
+        #(#attrs)*
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+        #visibility struct #ident;
+
+        impl #ident {
+            pub const CATEGORY: &'static str = #category;
+
+            pub fn category() -> ::aqueous::stream_name::Category {
+                ::aqueous::stream_name::Category::new(Self::CATEGORY)
+            }
+
+            pub fn stream_name(
+                id: impl ::std::convert::Into<::aqueous::stream_name::StreamID>,
+            ) -> ::aqueous::stream_name::StreamName {
+                Self::category().stream_name(id.into())
+            }
+
+            pub fn command_category() -> ::aqueous::stream_name::Category {
+                ::aqueous::stream_name::Category::new_command(Self::CATEGORY)
+            }
+
+            pub fn command_stream_name(
+                id: impl ::std::convert::Into<::aqueous::stream_name::StreamID>,
+            ) -> ::aqueous::stream_name::StreamName {
+                Self::command_category().stream_name(id.into())
+            }
+
+            pub fn position_category() -> ::aqueous::stream_name::Category {
+                ::aqueous::stream_name::Category::new_position(Self::CATEGORY)
+            }
+
+            /// `id` identifies the consumer whose position is recorded
+            pub fn position_stream_name(
+                id: impl ::std::convert::Into<::aqueous::stream_name::StreamID>,
+            ) -> ::aqueous::stream_name::StreamName {
+                Self::position_category().stream_name(id.into())
+            }
+
+            pub fn snapshot_category() -> ::aqueous::stream_name::Category {
+                ::aqueous::stream_name::Category::new_snapshot(Self::CATEGORY)
+            }
+
+            pub fn snapshot_stream_name(
+                id: impl ::std::convert::Into<::aqueous::stream_name::StreamID>,
+            ) -> ::aqueous::stream_name::StreamName {
+                Self::snapshot_category().stream_name(id.into())
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Path, Result};
+
+/// Implements `From<Source>` for each `#[copy(from = Source)]`, copying fields
+/// by name so that `Msg::follow` can be used between the two message types.
+/// `include(..)` or `exclude(..)` after a source name the fields of this
+/// struct copied from that source; fields not copied take their default.
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(unsupported(&input.ident)),
+        },
+        _ => return Err(unsupported(&input.ident)),
+    };
+
+    let sources = sources(&input.attrs)?;
+    if sources.is_empty() {
+        let message = "CopyFrom requires at least one #[copy(from = Source)] attribute";
+        return Err(Error::new_spanned(&input.ident, message));
+    }
+
+    let mut field_attributes = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("Named field");
+        field_attributes.push((ident, FieldAttributes::from_attrs(&field.attrs)?));
+    }
+
+    for source in &sources {
+        for field in source.include.iter().flatten().chain(&source.exclude) {
+            if !field_attributes.iter().any(|(ident, _)| *ident == field) {
+                let message = format!("{field} is not a field of {}", input.ident);
+                return Err(Error::new_spanned(field, message));
+            }
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut impls = Vec::new();
+    for source in &sources {
+        let mut field_values = Vec::new();
+
+        for (field, attributes) in &field_attributes {
+            let value = match attributes {
+                _ if attributes.skip || !source.copies(field) => {
+                    quote! { ::std::default::Default::default() }
+                }
+                FieldAttributes {
+                    rename: Some(rename),
+                    ..
+                } => {
+                    let source_ident = rename.parse::<Ident>()?;
+                    quote! { source.#source_ident }
+                }
+                FieldAttributes { .. } => quote! { source.#field },
+            };
+
+            field_values.push(quote! { #field: #value });
+        }
+
+        let path = &source.path;
+        impls.push(quote! {
+            impl #impl_generics ::std::convert::From<#path> for #ident #ty_generics #where_clause {
+                fn from(source: #path) -> Self {
+                    Self {
+                        #(#field_values,)*
+                    }
+                }
+            }
+        });
+    }
+
+    Ok(quote! { #(#impls)* })
+}
+
+fn unsupported(ident: &Ident) -> Error {
+    Error::new_spanned(
+        ident,
+        "CopyFrom can only be derived for structs with named fields",
+    )
+}
+
+struct Source {
+    path: Path,
+    include: Option<Vec<Ident>>,
+    exclude: Vec<Ident>,
+}
+
+impl Source {
+    fn copies(&self, field: &Ident) -> bool {
+        let included = match &self.include {
+            Some(include) => include.contains(field),
+            None => true,
+        };
+
+        included && !self.exclude.contains(field)
+    }
+}
+
+/// Each `#[copy(..)]` attribute on the struct names one source
+fn sources(attrs: &[Attribute]) -> Result<Vec<Source>> {
+    let mut sources = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("copy")) {
+        let mut path = None;
+        let mut include = None;
+        let mut exclude = Vec::new();
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("from") {
+                if path.is_some() {
+                    return Err(meta.error("each #[copy] attribute names a single source"));
+                }
+                path = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("include") {
+                let include = include.get_or_insert_with(Vec::new);
+                meta.parse_nested_meta(|field| {
+                    include.push(field.path.require_ident()?.clone());
+                    Ok(())
+                })
+            } else if meta.path.is_ident("exclude") {
+                meta.parse_nested_meta(|field| {
+                    exclude.push(field.path.require_ident()?.clone());
+                    Ok(())
+                })
+            } else {
+                Err(meta.error("unsupported copy attribute"))
+            }
+        })?;
+
+        let Some(path) = path else {
+            let message = "include and exclude apply to a #[copy(from = Source)] attribute";
+            return Err(Error::new_spanned(attr, message));
+        };
+
+        sources.push(Source {
+            path,
+            include,
+            exclude,
+        });
+    }
+
+    Ok(sources)
+}
+
+#[derive(Default)]
+struct FieldAttributes {
+    skip: bool,
+    rename: Option<LitStr>,
+}
+
+impl FieldAttributes {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("copy")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    attributes.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    attributes.rename = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported copy attribute"))
+                }
+            })?;
+        }
+
+        Ok(attributes)
+    }
+}
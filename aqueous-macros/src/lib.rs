@@ -1,25 +1,52 @@
+mod category;
+mod copy_from;
+mod message;
+mod message_set;
+mod rename;
+
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     token::Comma,
-    DeriveInput, Ident, Result,
+    DeriveInput, Error, Ident, Result,
 };
 
-#[proc_macro_derive(Message)]
+#[proc_macro_derive(Message, attributes(message))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let ident = &input.ident;
-    let ident_string = ident.to_string();
 
-    let tokens = quote! {
-        impl Message for #ident {
-            const TYPE_NAME: &'static str = #ident_string;
-        }
-    };
+    message::derive(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(MessageSet)]
+pub fn derive_message_set(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
 
-    TokenStream::from(tokens)
+    message_set::derive(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(CopyFrom, attributes(copy))]
+pub fn derive_copy_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    copy_from::derive(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Declares a component's category once, as a unit struct with typed
+/// constructors for its entity, command, position and snapshot streams
+#[proc_macro]
+pub fn category(input: TokenStream) -> TokenStream {
+    let declaration = parse_macro_input!(input as category::CategoryDeclaration);
+
+    category::expand(declaration).into()
 }
 
 // Copied from bevy_utils
@@ -50,7 +77,7 @@ impl Parse for AllTuples {
 /// invokes `impl_foo` providing ident tuples through arity `start..=end`.
 /// # Examples
 /// A single parameter.
-/// ```
+/// ```ignore
 /// use std::marker::PhantomData;
 /// use bevy_utils_proc_macros::all_tuples;
 ///
@@ -79,7 +106,7 @@ impl Parse for AllTuples {
 /// // impl_wrapped_in_foo!(P0 .. P14);
 /// ```
 /// Multiple parameters.
-/// ```
+/// ```ignore
 /// use bevy_utils_proc_macros::all_tuples;
 ///
 /// trait Append {
@@ -0,0 +1,86 @@
+use crate::rename::RenameRule;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, LitInt, LitStr, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    if let Data::Union(data) = &input.data {
+        let message = "Message cannot be derived for unions";
+        return Err(Error::new_spanned(data.union_token, message));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let attributes = MessageAttributes::from_input(&input)?;
+
+    let type_name = match (attributes.type_name, attributes.rename_all) {
+        (Some(type_name), _) => type_name.value(),
+        (None, Some(rename_all)) => rename_all.apply(&ident.to_string()),
+        (None, None) => ident.to_string(),
+    };
+
+    let aliases = attributes.aliases;
+    let aliases = (!aliases.is_empty()).then(|| {
+        quote! { const TYPE_NAME_ALIASES: &'static [&'static str] = &[#(#aliases),*]; }
+    });
+
+    let schema_version = attributes.schema_version.map(|schema_version| {
+        quote! { const SCHEMA_VERSION: u32 = #schema_version; }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::aqueous::message::Message for #ident #ty_generics #where_clause {
+            const TYPE_NAME: &'static str = #type_name;
+            #aliases
+            #schema_version
+        }
+    })
+}
+
+#[derive(Default)]
+struct MessageAttributes {
+    type_name: Option<LitStr>,
+    rename_all: Option<RenameRule>,
+    aliases: Vec<LitStr>,
+    schema_version: Option<LitInt>,
+}
+
+impl MessageAttributes {
+    fn from_input(input: &DeriveInput) -> Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("message"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("type_name") {
+                    attributes.type_name = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("rename_all") {
+                    let rule: LitStr = meta.value()?.parse()?;
+                    attributes.rename_all = Some(RenameRule::from_lit(&rule)?);
+                    Ok(())
+                } else if meta.path.is_ident("alias") {
+                    attributes.aliases.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("schema_version") {
+                    let schema_version: LitInt = meta.value()?.parse()?;
+                    if schema_version.base10_parse::<u32>()? < 1 {
+                        return Err(Error::new(
+                            schema_version.span(),
+                            "schema_version must be at least 1",
+                        ));
+                    }
+                    attributes.schema_version = Some(schema_version);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported message attribute"))
+                }
+            })?;
+        }
+
+        Ok(attributes)
+    }
+}
@@ -0,0 +1,67 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        let message = "MessageSet can only be derived for enums";
+        return Err(Error::new_spanned(&input.ident, message));
+    };
+
+    let ident = &input.ident;
+    let ident_string = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut variants = Vec::new();
+    let mut message_types = Vec::new();
+
+    for variant in &data.variants {
+        match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                variants.push(&variant.ident);
+                message_types.push(&fields.unnamed[0].ty);
+            }
+            _ => {
+                let message = "MessageSet variants must wrap a single message type";
+                return Err(Error::new_spanned(variant, message));
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::aqueous::message::MessageSet for #ident #ty_generics #where_clause {
+            const SET_NAME: &'static str = #ident_string;
+
+            fn message_type_name(&self) -> &'static str {
+                match self {
+                    #(Self::#variants(message) => {
+                        ::aqueous::message::MessageSet::message_type_name(message)
+                    })*
+                }
+            }
+
+            fn accepts_type_name(type_name: &str) -> bool {
+                false #(|| <#message_types as ::aqueous::message::MessageSet>::accepts_type_name(type_name))*
+            }
+
+            fn msg_from_data(
+                message_data: ::aqueous::message::MessageData,
+                casing: ::aqueous::message::Casing,
+                upcasters: &::aqueous::message::Upcasters,
+            ) -> ::std::result::Result<
+                ::aqueous::message::Msg<Self>,
+                ::std::boxed::Box<dyn ::std::error::Error>,
+            > {
+                #(
+                    if <#message_types as ::aqueous::message::MessageSet>::accepts_type_name(&message_data.type_name) {
+                        let msg = <#message_types as ::aqueous::message::MessageSet>::msg_from_data(message_data, casing, upcasters)?;
+                        return ::std::result::Result::Ok(msg.map(Self::#variants));
+                    }
+                )*
+
+                let error = ::std::format!("{} is not a message type of {}", message_data.type_name, #ident_string);
+                ::std::result::Result::Err(error.into())
+            }
+        }
+    })
+}
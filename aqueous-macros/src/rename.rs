@@ -0,0 +1,91 @@
+use syn::{Error, LitStr, Result};
+
+/// Casing rules named as in serde's `rename_all`
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub fn from_lit(lit: &LitStr) -> Result<Self> {
+        let rule = match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return Err(Error::new(lit.span(), "unknown rename rule")),
+        };
+
+        Ok(rule)
+    }
+
+    /// Applies the rule to a Pascal Rust identifier
+    pub fn apply(self, ident: &str) -> String {
+        let words = words(ident);
+
+        match self {
+            Self::Lower => words.concat().to_lowercase(),
+            Self::Upper => words.concat().to_uppercase(),
+            Self::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Self::Camel => {
+                let pascal = Self::Pascal.apply(ident);
+                let mut chars = pascal.chars();
+
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => pascal,
+                }
+            }
+            Self::Snake => words.join("_").to_lowercase(),
+            Self::ScreamingSnake => words.join("_").to_uppercase(),
+            Self::Kebab => words.join("-").to_lowercase(),
+            Self::ScreamingKebab => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+fn words(ident: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+
+    for (index, char) in ident.char_indices() {
+        if char == '_' {
+            if start < index {
+                words.push(&ident[start..index]);
+            }
+            start = index + 1;
+        } else if char.is_uppercase() && start < index {
+            words.push(&ident[start..index]);
+            start = index;
+        }
+    }
+
+    if start < ident.len() {
+        words.push(&ident[start..]);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.map(|c| c.to_ascii_lowercase()))
+            .collect(),
+        None => String::new(),
+    }
+}
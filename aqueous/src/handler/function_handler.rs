@@ -1,33 +1,70 @@
 use super::{Handler, HandlerParam};
-use crate::message::{Message, MessageData, Msg};
+use crate::message::{Casing, MessageData, MessageSet, Msg, Upcasters};
 use aqueous_macros::all_tuples;
 use async_trait::async_trait;
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 pub struct FunctionHandler<P, R, F> {
     func: F,
     params_marker: PhantomData<P>,
     return_marker: PhantomData<R>,
     message_type: &'static str,
+    casing: Casing,
+    upcasters: Arc<Upcasters>,
 }
 
 impl<P, R, F> FunctionHandler<P, R, F> {
     pub fn message_type(&self) -> &'static str {
         self.message_type
     }
+
+    /// The casing handled messages are stored in
+    pub fn casing(mut self, casing: Casing) -> Self {
+        self.casing = casing;
+        self
+    }
+
+    /// Upcasts messages written with older schema versions before they are
+    /// handled. Without upcasters, only current schema versions are handled.
+    pub fn upcasters(mut self, upcasters: Arc<Upcasters>) -> Self {
+        self.upcasters = upcasters;
+        self
+    }
 }
 
 pub trait IntoHandler<P, R, F>: Sized {
     fn into_handler(self) -> FunctionHandler<P, R, F>;
 }
 
+macro_rules! impl_into_handler {
+    ($($ty:ident $(,)?)*) => {
+        impl<M, F, R, $($ty,)*> IntoHandler<(Msg<M>, $($ty,)*), R, F> for F
+        where
+            M: MessageSet,
+            F: Fn(Msg<M>, $($ty,)*) -> R,
+        {
+            fn into_handler(self) -> FunctionHandler<(Msg<M>, $($ty,)*), R, F> {
+                FunctionHandler {
+                    func: self,
+                    params_marker: Default::default(),
+                    return_marker: Default::default(),
+                    message_type: M::SET_NAME,
+                    casing: Casing::default(),
+                    upcasters: Default::default(),
+                }
+            }
+        }
+    }
+}
+all_tuples!(impl_into_handler, T);
+
 macro_rules! impl_handler {
    ($($ty:ident $(,)?)*) => {
         #[allow(non_snake_case)]
         #[async_trait]
         impl<M, $($ty,)* F, R, C, S> Handler<C, S> for FunctionHandler<(Msg<M>, $($ty,)*), R, F>
         where
-            for<'de> M: Message + serde::Deserialize<'de> + Send,
+            M: MessageSet + Send,
             $($ty: HandlerParam<C, S> + Send,)*
             F: Fn(Msg<M>, $($ty,)*) -> R + Send,
             R: std::future::Future<Output = ()> + Send,
@@ -35,8 +72,8 @@ macro_rules! impl_handler {
             S: Clone + Send + 'static,
         {
             async fn call(&mut self, message_data: MessageData, _connection: C, _settings: S) -> bool {
-                if &message_data.type_name == M::TYPE_NAME {
-                    let msg: Msg<M> = Msg::from_data(message_data.clone()).unwrap();
+                if M::accepts_type_name(&message_data.type_name) {
+                    let msg: Msg<M> = M::msg_from_data(message_data.clone(), self.casing, &self.upcasters).unwrap();
                     $(let $ty = $ty::build(_connection.clone(), _settings.clone()).await;)*
                     (self.func)(msg, $($ty,)*).await;
                     true
@@ -0,0 +1,163 @@
+use crate::{
+    message::{Message, MessageData, Msg},
+    message_store::MessageStore,
+    stream_name::{Category, StreamName},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, error::Error};
+use thiserror::Error;
+
+/// The global position of the last message an entity processed from each
+/// source category, recorded on the entity to guard against reprocessing
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProcessedPositions(HashMap<Category, i64>);
+
+impl ProcessedPositions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn position(&self, category: &Category) -> Option<i64> {
+        self.0.get(category).copied()
+    }
+
+    /// Whether a message read from a source category was already processed.
+    /// Messages without a stream name or global position never are.
+    pub fn is_processed<T>(&self, msg: &Msg<T>) -> bool {
+        let metadata = &msg.metadata;
+        let source = metadata.stream_name().zip(metadata.global_position());
+
+        let Some((stream_name, global_position)) = source else {
+            return false;
+        };
+        let category = stream_name.category();
+
+        match self.position(&category) {
+            Some(position) => global_position <= position,
+            None => false,
+        }
+    }
+
+    /// Records the source message that caused an event, as written to the
+    /// event's metadata by `Msg::follow`, when the event is projected
+    pub fn record<T>(&mut self, msg: &Msg<T>) {
+        let metadata = &msg.metadata;
+        let causation = metadata
+            .causation_message_stream_name()
+            .zip(metadata.causation_message_global_position());
+
+        let Some((stream_name, global_position)) = causation else {
+            return;
+        };
+        let category = stream_name.category();
+
+        let position = self.0.entry(category).or_insert(global_position);
+        *position = global_position.max(*position);
+    }
+}
+
+pub trait Idempotent {
+    fn processed_positions(&self) -> &ProcessedPositions;
+
+    fn processed_positions_mut(&mut self) -> &mut ProcessedPositions;
+
+    fn is_processed<T>(&self, msg: &Msg<T>) -> bool {
+        self.processed_positions().is_processed(msg)
+    }
+
+    fn record_processed<T>(&mut self, msg: &Msg<T>) {
+        self.processed_positions_mut().record(msg)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SequenceError {
+    #[error(
+        "Message at sequence {sequence} was already processed (entity sequence {entity_sequence})"
+    )]
+    AlreadyProcessed { sequence: i64, entity_sequence: i64 },
+    #[error("Message has no global position to be sequenced by")]
+    MissingGlobalPosition,
+}
+
+/// A message following one that caused it, to be written only if the entity's
+/// stream is still at `expected_version`, or still has no messages when `None`.
+/// The causing message's global position is the message's causation global
+/// position, recorded on the entity as its sequence when the message is projected.
+#[derive(Debug)]
+pub struct SequencedWrite<T> {
+    pub msg: Msg<T>,
+    pub expected_version: Option<i64>,
+}
+
+impl<T> SequencedWrite<T>
+where
+    T: Message + Serialize,
+{
+    /// Fails without writing when another message was written to the entity's
+    /// stream since it was projected, so the same sequence is never written twice
+    pub async fn write<S>(self, store: &S, stream_name: &StreamName) -> Result<i64, Box<dyn Error>>
+    where
+        S: MessageStore + Sync,
+    {
+        let message_data = MessageData::try_from(self.msg)?;
+        let expected_version = self.expected_version.unwrap_or(-1);
+
+        let position = store
+            .write_message(&message_data, stream_name, Some(expected_version))
+            .await?;
+
+        Ok(position)
+    }
+}
+
+/// Entities that store the highest sequence they have processed
+pub trait Sequenced {
+    fn sequence(&self) -> Option<i64>;
+
+    /// The sequence an event written by `follow_sequenced` carries, for the
+    /// projection to store as the entity's sequence
+    fn event_sequence<T>(event: &Msg<T>) -> Option<i64>
+    where
+        Self: Sized,
+    {
+        event.metadata.causation_message_global_position()
+    }
+
+    fn is_sequence_processed<T>(&self, msg: &Msg<T>) -> bool {
+        match (self.sequence(), msg.metadata.global_position()) {
+            (Some(entity_sequence), Some(sequence)) => sequence <= entity_sequence,
+            _ => false,
+        }
+    }
+
+    /// `version` is the entity stream's version the entity was projected at,
+    /// or `None` when the stream has no messages
+    fn follow_sequenced<M, T>(
+        &self,
+        msg: Msg<M>,
+        version: Option<i64>,
+    ) -> Result<SequencedWrite<T>, SequenceError>
+    where
+        T: Message + From<M>,
+    {
+        let sequence = msg
+            .metadata
+            .global_position()
+            .ok_or(SequenceError::MissingGlobalPosition)?;
+
+        if let Some(entity_sequence) = self.sequence() {
+            if sequence <= entity_sequence {
+                return Err(SequenceError::AlreadyProcessed {
+                    sequence,
+                    entity_sequence,
+                });
+            }
+        }
+
+        Ok(SequencedWrite {
+            msg: Msg::follow(msg),
+            expected_version: version,
+        })
+    }
+}
@@ -1,3 +1,8 @@
+// Lets `aqueous-macros` expand to `::aqueous` paths inside this crate too
+extern crate self as aqueous;
+
 pub mod handler;
+pub mod idempotence;
 pub mod message;
+pub mod message_store;
 pub mod stream_name;
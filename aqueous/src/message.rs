@@ -1,15 +1,81 @@
+mod casing;
+mod copy;
+mod format;
 mod message_data;
 mod metadata;
 mod msg;
+mod msg_builder;
+mod replay;
+mod upcasters;
 
+pub use casing::*;
+pub use copy::*;
+pub use format::*;
 pub use message_data::*;
 pub use metadata::*;
 pub use msg::*;
+pub use msg_builder::*;
+pub use replay::*;
+pub use upcasters::*;
+
+pub use aqueous_macros::{CopyFrom, Message, MessageSet};
+
+use serde::de::DeserializeOwned;
+use std::error::Error;
 
 pub trait Message: Sized {
     const TYPE_NAME: &'static str;
+    /// Legacy type names that are still accepted when reading messages
+    const TYPE_NAME_ALIASES: &'static [&'static str] = &[];
+    const SCHEMA_VERSION: u32 = 1;
 
     fn type_name(&self) -> &'static str {
         Self::TYPE_NAME
     }
+
+    fn is_type_name(type_name: &str) -> bool {
+        type_name == Self::TYPE_NAME || Self::TYPE_NAME_ALIASES.contains(&type_name)
+    }
+}
+
+/// Message types that a handler can receive. Every deserializable `Message` is a
+/// set of one; `#[derive(MessageSet)]` implements it for enums of message types.
+pub trait MessageSet: Sized {
+    /// The message's type name, or the enum's name for a derived set
+    const SET_NAME: &'static str;
+
+    fn message_type_name(&self) -> &'static str;
+
+    fn accepts_type_name(type_name: &str) -> bool;
+
+    /// Upcasts the data to the current schema version of its message type
+    /// before it is deserialized from the stored casing
+    fn msg_from_data(
+        message_data: MessageData,
+        casing: Casing,
+        upcasters: &Upcasters,
+    ) -> Result<Msg<Self>, Box<dyn Error>>;
+}
+
+impl<M> MessageSet for M
+where
+    M: Message + DeserializeOwned,
+{
+    const SET_NAME: &'static str = M::TYPE_NAME;
+
+    fn message_type_name(&self) -> &'static str {
+        Message::type_name(self)
+    }
+
+    fn accepts_type_name(type_name: &str) -> bool {
+        <M as Message>::is_type_name(type_name)
+    }
+
+    fn msg_from_data(
+        message_data: MessageData,
+        casing: Casing,
+        upcasters: &Upcasters,
+    ) -> Result<Msg<Self>, Box<dyn Error>> {
+        Msg::upcast_cased(message_data, casing, upcasters)
+    }
 }
@@ -0,0 +1,123 @@
+mod de;
+mod ser;
+
+use super::Metadata;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+/// Casing of the keys of stored message data and metadata. Rust messages are
+/// always snake_case; other casings are converted to and from when encoding
+/// and decoding `MessageData`.
+///
+/// Only struct field names are converted, never the keys of maps within the
+/// data. Decoding matches stored keys against the target's own field names,
+/// so field names that the casing would otherwise lose, such as `line_1` or
+/// `_tag`, still round-trip.
+///
+/// Internally tagged, adjacently tagged and untagged enums, and structs with
+/// flattened fields, are buffered by serde without their field names, so
+/// they are rejected by casings other than snake_case.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Casing {
+    #[default]
+    Snake,
+    Camel,
+    Pascal,
+    Kebab,
+}
+
+impl Casing {
+    /// Converts a snake_case field name the way serde's `rename_all` does
+    pub fn from_snake_case(self, field: &str) -> String {
+        match self {
+            Self::Snake => field.to_owned(),
+            Self::Kebab => field.replace('_', "-"),
+            Self::Pascal => {
+                let mut pascal_case = String::with_capacity(field.len());
+                let mut capitalize = true;
+
+                for char in field.chars() {
+                    if char == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal_case.extend(char.to_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal_case.push(char);
+                    }
+                }
+
+                pascal_case
+            }
+            Self::Camel => {
+                let pascal_case = Self::Pascal.from_snake_case(field);
+                let mut chars = pascal_case.chars();
+
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => pascal_case,
+                }
+            }
+        }
+    }
+
+    /// Serializes message data with its struct field names in the stored casing.
+    /// Fails if two fields of a struct convert to the same key.
+    pub fn encode<T>(self, data: &T) -> Result<Value, serde_json::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        match self {
+            Self::Snake => serde_json::to_value(data),
+            casing => data.serialize(ser::Serializer::new(casing)),
+        }
+    }
+
+    /// Deserializes message data whose struct field names are in the stored casing
+    pub fn decode<T>(self, data: Value) -> Result<T, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            Self::Snake => serde_json::from_value(data),
+            casing => T::deserialize(de::Deserializer::new(data, casing)),
+        }
+    }
+
+    /// Converts the keys aqueous writes to metadata. Keys set by applications
+    /// and property names are left as they are.
+    pub fn encode_metadata(self, metadata: Metadata) -> Metadata {
+        self.convert_metadata(metadata, |key| {
+            Metadata::KEYS
+                .contains(&key)
+                .then(|| self.from_snake_case(key))
+        })
+    }
+
+    pub fn decode_metadata(self, metadata: Metadata) -> Metadata {
+        self.convert_metadata(metadata, |key| {
+            Metadata::KEYS
+                .iter()
+                .find(|metadata_key| self.from_snake_case(metadata_key) == key)
+                .map(|metadata_key| metadata_key.to_string())
+        })
+    }
+
+    fn convert_metadata(
+        self,
+        metadata: Metadata,
+        convert_key: impl Fn(&str) -> Option<String>,
+    ) -> Metadata {
+        if self == Self::Snake {
+            return metadata;
+        }
+
+        let map = metadata
+            .map
+            .into_iter()
+            .map(|(key, value)| (convert_key(&key).unwrap_or(key), value))
+            .collect::<Map<_, _>>();
+
+        Metadata::from(map)
+    }
+}
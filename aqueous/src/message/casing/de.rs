@@ -0,0 +1,339 @@
+use super::Casing;
+use serde::{
+    de::{self, DeserializeSeed, Error as _, Visitor},
+    forward_to_deserialize_any, Deserialize,
+};
+use serde_json::{map, Error, Map, Value};
+use std::{any::type_name, vec};
+
+/// Whether serde is buffering the value to deserialize it later, as it does for
+/// tagged and untagged enums, when the target's field names are not passed on
+fn is_buffered<V>() -> bool {
+    let visitor = type_name::<V>();
+
+    visitor.contains("::content::") && visitor.contains("ContentVisitor")
+}
+
+/// Deserializes from a `Value` like `serde_json`, matching the keys of structs
+/// against their field names in the casing
+pub(super) struct Deserializer {
+    value: Value,
+    casing: Casing,
+}
+
+impl Deserializer {
+    pub(super) fn new(value: Value, casing: Casing) -> Self {
+        Self { value, casing }
+    }
+
+    fn visit_object<'de, V>(
+        map: Map<String, Value>,
+        casing: Casing,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let len = map.len();
+        let mut map_access = MapAccess {
+            entries: map.into_iter(),
+            value: None,
+            casing,
+            fields,
+        };
+
+        let value = visitor.visit_map(&mut map_access)?;
+
+        match map_access.entries.len() {
+            0 => Ok(value),
+            _ => Err(Error::invalid_length(len, &"fewer elements in map")),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Array(values) => {
+                let len = values.len();
+                let mut seq_access = SeqAccess {
+                    values: values.into_iter(),
+                    casing: self.casing,
+                };
+
+                let value = visitor.visit_seq(&mut seq_access)?;
+
+                match seq_access.values.len() {
+                    0 => Ok(value),
+                    _ => Err(Error::invalid_length(len, &"fewer elements in array")),
+                }
+            }
+            Value::Object(..) if is_buffered::<V>() => Err(Error::custom(format_args!(
+                "tagged and untagged enums are buffered before their fields are \
+                 known, so they cannot be decoded from {:?} case",
+                self.casing
+            ))),
+            Value::Object(map) => Self::visit_object(map, self.casing, &[], visitor),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        // Derived structs are only deserialized as maps when they have flattened fields
+        if type_name::<V>().contains("::__Visitor") {
+            return Err(Error::custom(format_args!(
+                "structs with flattened fields cannot be decoded from {:?} case",
+                self.casing
+            )));
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Object(map) => Self::visit_object(map, self.casing, fields, visitor),
+            Value::Array(..) => self.deserialize_any(visitor),
+            value => value.deserialize_struct(name, fields, visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Object(map) if map.len() == 1 => {
+                let (variant, value) = map.into_iter().next().unwrap();
+
+                visitor.visit_enum(EnumAccess {
+                    variant,
+                    value,
+                    casing: self.casing,
+                })
+            }
+            value => value.deserialize_enum(name, variants, visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct identifier
+        ignored_any
+    }
+}
+
+struct SeqAccess {
+    values: vec::IntoIter<Value>,
+    casing: Casing,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.values.next() {
+            Some(value) => seed
+                .deserialize(Deserializer::new(value, self.casing))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Keys that match no field, including every key of a map, are passed on as they are
+struct MapAccess {
+    entries: map::IntoIter,
+    value: Option<Value>,
+    casing: Casing,
+    fields: &'static [&'static str],
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+
+        let field = self
+            .fields
+            .iter()
+            .find(|field| self.casing.from_snake_case(field) == key);
+
+        match field {
+            Some(field) => seed.deserialize(MapKey(field.to_string())).map(Some),
+            None => seed.deserialize(MapKey(key)).map(Some),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::custom("value is missing"))?;
+
+        seed.deserialize(Deserializer::new(value, self.casing))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Object keys are always strings, but map keys may be numbers
+struct MapKey(String);
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.0.parse() {
+                    Ok(key) => visitor.$visit(key),
+                    Err(..) => visitor.visit_string(self.0),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MapKey {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        Value::String(self.0).deserialize_enum(name, variants, visitor)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumAccess {
+    variant: String,
+    value: Value,
+    casing: Casing,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess), Error> {
+        let variant = seed.deserialize(MapKey(self.variant))?;
+        let variant_access = VariantAccess {
+            value: self.value,
+            casing: self.casing,
+        };
+
+        Ok((variant, variant_access))
+    }
+}
+
+struct VariantAccess {
+    value: Value,
+    casing: Casing,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        <()>::deserialize(self.value)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(Deserializer::new(self.value, self.casing))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(Deserializer::new(self.value, self.casing), visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_struct(
+            Deserializer::new(self.value, self.casing),
+            "",
+            fields,
+            visitor,
+        )
+    }
+}
@@ -0,0 +1,66 @@
+use serde_json::{Map, Value};
+
+/// Selects and renames the fields copied from one message's data to another's
+#[derive(Clone, Debug, Default)]
+pub struct CopyFields {
+    include: Option<Vec<String>>,
+    exclude: Vec<String>,
+    rename: Vec<(String, String)>,
+}
+
+impl CopyFields {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copies only the given fields; all fields are copied by default
+    pub fn include<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: ToString,
+    {
+        let fields = fields.into_iter().map(|field| field.to_string());
+        self.include.get_or_insert_with(Vec::new).extend(fields);
+        self
+    }
+
+    pub fn exclude<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: ToString,
+    {
+        let fields = fields.into_iter().map(|field| field.to_string());
+        self.exclude.extend(fields);
+        self
+    }
+
+    pub fn rename(mut self, from: impl ToString, to: impl ToString) -> Self {
+        self.rename.push((from.to_string(), to.to_string()));
+        self
+    }
+
+    pub fn apply(&self, data: Value) -> Value {
+        let Value::Object(map) = data else {
+            return data;
+        };
+
+        map.into_iter()
+            .filter(|(field, _)| match &self.include {
+                Some(include) => include.contains(field),
+                None => true,
+            })
+            .filter(|(field, _)| !self.exclude.contains(field))
+            .map(|(field, value)| {
+                let field = self
+                    .rename
+                    .iter()
+                    .find(|(from, _)| *from == field)
+                    .map(|(_, to)| to.clone())
+                    .unwrap_or(field);
+
+                (field, value)
+            })
+            .collect::<Map<_, _>>()
+            .into()
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FormatError {
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[cfg(feature = "msgpack")]
+    #[error(transparent)]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+    #[cfg(feature = "msgpack")]
+    #[error(transparent)]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+    #[cfg(feature = "cbor")]
+    #[error(transparent)]
+    CborEncode(#[from] ciborium::ser::Error<std::io::Error>),
+    #[cfg(feature = "cbor")]
+    #[error(transparent)]
+    CborDecode(#[from] ciborium::de::Error<std::io::Error>),
+}
+
+/// Encoding of message data and metadata for storage and transport. Stores
+/// encode `MessageData` with its `encode_data` and `encode_metadata`, or typed
+/// messages straight from their data with `Msg::encode` and `Msg::decode`.
+/// Handlers receive `MessageData`, whose values do not depend on the format.
+pub trait Format {
+    fn serialize<T>(value: &T) -> Result<Vec<u8>, FormatError>
+    where
+        T: Serialize + ?Sized;
+
+    fn deserialize<T>(bytes: &[u8]) -> Result<T, FormatError>
+    where
+        T: DeserializeOwned;
+}
+
+pub struct Json;
+
+impl Format for Json {
+    fn serialize<T>(value: &T) -> Result<Vec<u8>, FormatError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let bytes = serde_json::to_vec(value)?;
+        Ok(bytes)
+    }
+
+    fn deserialize<T>(bytes: &[u8]) -> Result<T, FormatError>
+    where
+        T: DeserializeOwned,
+    {
+        let value = serde_json::from_slice(bytes)?;
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+pub struct MessagePack;
+
+#[cfg(feature = "msgpack")]
+impl Format for MessagePack {
+    fn serialize<T>(value: &T) -> Result<Vec<u8>, FormatError>
+    where
+        T: Serialize + ?Sized,
+    {
+        // Field names are kept so that data can be read back into a `serde_json::Value`
+        let bytes = rmp_serde::to_vec_named(value)?;
+        Ok(bytes)
+    }
+
+    fn deserialize<T>(bytes: &[u8]) -> Result<T, FormatError>
+    where
+        T: DeserializeOwned,
+    {
+        let value = rmp_serde::from_slice(bytes)?;
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "cbor")]
+pub struct Cbor;
+
+#[cfg(feature = "cbor")]
+impl Format for Cbor {
+    fn serialize<T>(value: &T) -> Result<Vec<u8>, FormatError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn deserialize<T>(bytes: &[u8]) -> Result<T, FormatError>
+    where
+        T: DeserializeOwned,
+    {
+        let value = ciborium::from_reader(bytes)?;
+        Ok(value)
+    }
+}
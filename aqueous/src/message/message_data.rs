@@ -1,25 +1,68 @@
-use super::{Message, Metadata, Msg};
-use serde::Serialize;
+use super::{Casing, Format, FormatError, Message, Metadata, Msg};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use uuid::Uuid;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MessageData {
+    pub id: Uuid,
     pub type_name: String,
     pub metadata: Metadata,
     pub data: Value,
 }
 
-impl<M: Message + Serialize> TryFrom<Msg<M>> for MessageData {
-    type Error = Box<dyn std::error::Error>;
+impl MessageData {
+    pub fn try_from_cased<M>(
+        message: Msg<M>,
+        casing: Casing,
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        M: Message + Serialize,
+    {
+        let data = casing.encode(&message.data)?;
+        let metadata = Metadata::for_write(message.metadata).set_schema_version(M::SCHEMA_VERSION);
 
-    fn try_from(message: Msg<M>) -> Result<Self, Self::Error> {
-        let data = serde_json::to_value(&message.data)?;
-        let msg = Self {
+        let message_data = Self {
+            id: message.id,
             data,
             type_name: M::TYPE_NAME.to_string(),
-            metadata: Metadata::follow(message.metadata),
+            metadata: casing.encode_metadata(metadata),
         };
 
-        Ok(msg)
+        Ok(message_data)
+    }
+
+    pub fn encode_data<F: Format>(&self) -> Result<Vec<u8>, FormatError> {
+        F::serialize(&self.data)
+    }
+
+    pub fn encode_metadata<F: Format>(&self) -> Result<Vec<u8>, FormatError> {
+        F::serialize(&self.metadata)
+    }
+
+    pub fn decode<F: Format>(
+        id: Uuid,
+        type_name: impl ToString,
+        metadata: &[u8],
+        data: &[u8],
+    ) -> Result<Self, FormatError> {
+        let type_name = type_name.to_string();
+        let metadata = F::deserialize(metadata)?;
+        let data = F::deserialize(data)?;
+
+        Ok(Self {
+            id,
+            type_name,
+            metadata,
+            data,
+        })
+    }
+}
+
+impl<M: Message + Serialize> TryFrom<Msg<M>> for MessageData {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(message: Msg<M>) -> Result<Self, Self::Error> {
+        Self::try_from_cased(message, Casing::default())
     }
 }
@@ -1,10 +1,19 @@
+use super::MetadataError;
 use crate::stream_name::StreamName;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{Map, Value};
-use time::PrimitiveDateTime;
+use time::{format_description::well_known::Iso8601, PrimitiveDateTime};
 
-#[derive(Clone, Debug, Serialize)]
-pub struct Metadata(pub Map<String, Value>);
+/// A message's metadata, serialized as the map of its keys. Local properties
+/// are carried in-process only: they are never serialized, and are dropped
+/// when a message is followed or written.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Metadata {
+    pub map: Map<String, Value>,
+    #[serde(skip)]
+    local_properties: Map<String, Value>,
+}
 
 impl Metadata {
     pub const CAUSATION_MESSAGE_STREAM_NAME_KEY: &'static str = "causation_message_stream_name";
@@ -12,17 +21,39 @@ impl Metadata {
     pub const CAUSATION_MESSAGE_GLOBAL_POSITION_KEY: &'static str =
         "causation_message_global_position";
     pub const CORRELATION_STREAM_NAME_KEY: &'static str = "correlation_stream_name";
+    pub const REPLY_STREAM_NAME_KEY: &'static str = "reply_stream_name";
     pub const POSITION_KEY: &'static str = "position";
     pub const GLOBAL_POSITION_KEY: &'static str = "global_position";
     pub const STREAM_NAME_KEY: &'static str = "stream_name";
-    pub const REPLAY_STREAM_NAME_KEY: &'static str = "correlation_stream_name";
+    pub const REPLAY_STREAM_NAME_KEY: &'static str = "replay_stream_name";
     pub const TIME_KEY: &'static str = "time";
+    pub const PROPERTIES_KEY: &'static str = "properties";
+    pub const SCHEMA_VERSION_KEY: &'static str = "schema_version";
+
+    pub const KEYS: &'static [&'static str] = &[
+        Self::CAUSATION_MESSAGE_STREAM_NAME_KEY,
+        Self::CAUSATION_MESSAGE_POSITION_KEY,
+        Self::CAUSATION_MESSAGE_GLOBAL_POSITION_KEY,
+        Self::CORRELATION_STREAM_NAME_KEY,
+        Self::REPLY_STREAM_NAME_KEY,
+        Self::POSITION_KEY,
+        Self::GLOBAL_POSITION_KEY,
+        Self::STREAM_NAME_KEY,
+        Self::REPLAY_STREAM_NAME_KEY,
+        Self::TIME_KEY,
+        Self::PROPERTIES_KEY,
+        Self::SCHEMA_VERSION_KEY,
+    ];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
 
     pub fn get_as<T>(&self, key: &str) -> Option<T>
     where
         T: DeserializeOwned,
     {
-        let value = self.0.get(key)?;
+        let value = self.map.get(key)?;
         serde_json::from_value(value.clone()).ok()
     }
 
@@ -30,70 +61,259 @@ impl Metadata {
     where
         Value: From<T>,
     {
-        self.0.insert(key.to_owned(), value.into());
+        self.map.insert(key.to_owned(), value.into());
         self
     }
 
+    /// Where the followed message was written becomes the following message's
+    /// causation. Only messages that follow another message should use this;
+    /// writing a message as it is uses `for_write`
     pub fn follow(metadata: Metadata) -> Self {
-        let Metadata(mut map) = metadata;
+        let mut map = metadata.map;
+
+        let causation = [
+            (
+                Self::STREAM_NAME_KEY,
+                Self::CAUSATION_MESSAGE_STREAM_NAME_KEY,
+            ),
+            (Self::POSITION_KEY, Self::CAUSATION_MESSAGE_POSITION_KEY),
+            (
+                Self::GLOBAL_POSITION_KEY,
+                Self::CAUSATION_MESSAGE_GLOBAL_POSITION_KEY,
+            ),
+        ];
+
+        for (key, causation_key) in causation {
+            if let Some(value) = map.remove(key) {
+                map.insert(causation_key.to_owned(), value);
+            }
+        }
 
-        map.remove(Self::POSITION_KEY);
-        map.remove(Self::GLOBAL_POSITION_KEY);
         map.remove(Self::TIME_KEY);
-        map.remove(Self::STREAM_NAME_KEY);
 
-        Self(map)
+        // Messages that follow a replayed message are new messages, not replays
+        map.remove(Self::REPLAY_STREAM_NAME_KEY);
+
+        Self::from(map)
+    }
+
+    /// Removes the keys the message store sets when a message is read, leaving
+    /// the message's own causation as it is
+    pub fn for_write(metadata: Metadata) -> Self {
+        let mut map = metadata.map;
+
+        for key in [
+            Self::STREAM_NAME_KEY,
+            Self::POSITION_KEY,
+            Self::GLOBAL_POSITION_KEY,
+            Self::TIME_KEY,
+        ] {
+            map.remove(key);
+        }
+
+        Self::from(map)
+    }
+
+    pub fn properties(&self) -> Option<&Map<String, Value>> {
+        self.map.get(Self::PROPERTIES_KEY)?.as_object()
+    }
+
+    pub fn get_property<T>(&self, name: &str) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let value = self.properties()?.get(name)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    pub fn set_property<T>(mut self, name: &str, value: T) -> Self
+    where
+        Value: From<T>,
+    {
+        let properties = self
+            .map
+            .entry(Self::PROPERTIES_KEY)
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        if !properties.is_object() {
+            *properties = Value::Object(Map::new());
+        }
+
+        if let Value::Object(properties) = properties {
+            properties.insert(name.to_owned(), value.into());
+        }
+
+        self
+    }
+
+    pub fn local_properties(&self) -> &Map<String, Value> {
+        &self.local_properties
+    }
+
+    pub fn get_local_property<T>(&self, name: &str) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let value = self.local_properties.get(name)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    pub fn set_local_property<T>(mut self, name: &str, value: T) -> Self
+    where
+        Value: From<T>,
+    {
+        self.local_properties.insert(name.to_owned(), value.into());
+        self
     }
 
     pub fn position(&self) -> Option<i64> {
-        let value = self.0.get(Self::POSITION_KEY)?;
+        let value = self.map.get(Self::POSITION_KEY)?;
         serde_json::from_value(value.clone()).ok()
     }
 
     pub fn set_position(mut self, position: i64) -> Self {
         let key = String::from(Self::POSITION_KEY);
-        self.0.insert(key, position.into());
+        self.map.insert(key, position.into());
         self
     }
 
     pub fn global_position(&self) -> Option<i64> {
-        let value = self.0.get(Self::GLOBAL_POSITION_KEY)?;
+        let value = self.map.get(Self::GLOBAL_POSITION_KEY)?;
         serde_json::from_value(value.clone()).ok()
     }
 
     pub fn set_global_position(mut self, global_position: i64) -> Self {
         let key = String::from(Self::GLOBAL_POSITION_KEY);
-        self.0.insert(key, global_position.into());
+        self.map.insert(key, global_position.into());
         self
     }
 
     pub fn stream_name(&self) -> Option<StreamName> {
-        let value = self.0.get(Self::STREAM_NAME_KEY)?;
-        let stream_name = serde_json::from_value(value.clone()).ok()?;
-
-        Some(StreamName(stream_name))
+        self.get_as(Self::STREAM_NAME_KEY)
     }
 
     pub fn set_stream_name(mut self, stream_name: StreamName) -> Self {
         let key = String::from(Self::STREAM_NAME_KEY);
-        self.0.insert(key, stream_name.0.into());
+        self.map.insert(key, stream_name.0.into());
         self
     }
 
+    pub fn causation_message_stream_name(&self) -> Option<StreamName> {
+        self.get_as(Self::CAUSATION_MESSAGE_STREAM_NAME_KEY)
+    }
+
+    pub fn set_causation_message_stream_name(mut self, stream_name: StreamName) -> Self {
+        let key = String::from(Self::CAUSATION_MESSAGE_STREAM_NAME_KEY);
+        self.map.insert(key, stream_name.0.into());
+        self
+    }
+
+    pub fn causation_message_position(&self) -> Option<i64> {
+        self.get_as(Self::CAUSATION_MESSAGE_POSITION_KEY)
+    }
+
+    pub fn set_causation_message_position(mut self, position: i64) -> Self {
+        let key = String::from(Self::CAUSATION_MESSAGE_POSITION_KEY);
+        self.map.insert(key, position.into());
+        self
+    }
+
+    pub fn causation_message_global_position(&self) -> Option<i64> {
+        self.get_as(Self::CAUSATION_MESSAGE_GLOBAL_POSITION_KEY)
+    }
+
+    pub fn set_causation_message_global_position(mut self, global_position: i64) -> Self {
+        let key = String::from(Self::CAUSATION_MESSAGE_GLOBAL_POSITION_KEY);
+        self.map.insert(key, global_position.into());
+        self
+    }
+
+    pub fn correlation_stream_name(&self) -> Option<StreamName> {
+        self.get_as(Self::CORRELATION_STREAM_NAME_KEY)
+    }
+
+    pub fn set_correlation_stream_name(mut self, stream_name: StreamName) -> Self {
+        let key = String::from(Self::CORRELATION_STREAM_NAME_KEY);
+        self.map.insert(key, stream_name.0.into());
+        self
+    }
+
+    pub fn reply_stream_name(&self) -> Option<StreamName> {
+        self.get_as(Self::REPLY_STREAM_NAME_KEY)
+    }
+
+    pub fn set_reply_stream_name(mut self, stream_name: StreamName) -> Self {
+        let key = String::from(Self::REPLY_STREAM_NAME_KEY);
+        self.map.insert(key, stream_name.0.into());
+        self
+    }
+
+    /// The stream a replayed message was originally written to
+    pub fn replay_stream_name(&self) -> Option<StreamName> {
+        self.get_as(Self::REPLAY_STREAM_NAME_KEY)
+    }
+
+    pub fn set_replay_stream_name(mut self, stream_name: StreamName) -> Self {
+        let key = String::from(Self::REPLAY_STREAM_NAME_KEY);
+        self.map.insert(key, stream_name.0.into());
+        self
+    }
+
+    pub fn is_replay(&self) -> bool {
+        self.map.contains_key(Self::REPLAY_STREAM_NAME_KEY)
+    }
+
     pub fn time(&self) -> Option<PrimitiveDateTime> {
-        let value = self.0.get(Self::TIME_KEY)?;
-        serde_json::from_value(value.clone()).ok()
+        let value = self.map.get(Self::TIME_KEY)?;
+
+        // Written by `set_time` in time's serde shape, or by the message store as ISO 8601
+        match value {
+            Value::String(time) => PrimitiveDateTime::parse(time, &Iso8601::DEFAULT).ok(),
+            value => serde_json::from_value(value.clone()).ok(),
+        }
     }
 
     pub fn set_time(mut self, time: PrimitiveDateTime) -> Self {
         let key = String::from(Self::TIME_KEY);
         let value = serde_json::to_value(time).unwrap();
-        self.0.insert(key, value);
+        self.map.insert(key, value);
 
         self
     }
 
+    /// Messages written before schema versioning are treated as version 1, but
+    /// a version that is present and malformed is an error rather than version 1
+    pub fn schema_version(&self) -> Result<u32, MetadataError> {
+        let Some(value) = self.map.get(Self::SCHEMA_VERSION_KEY) else {
+            return Ok(1);
+        };
+
+        let schema_version = value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok());
+
+        match schema_version {
+            Some(schema_version) if schema_version >= 1 => Ok(schema_version),
+            _ => Err(MetadataError::InvalidSchemaVersion(value.clone())),
+        }
+    }
+
+    pub fn set_schema_version(mut self, schema_version: u32) -> Self {
+        let key = String::from(Self::SCHEMA_VERSION_KEY);
+        self.map.insert(key, schema_version.into());
+        self
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.map.is_empty()
+    }
+}
+
+impl From<Map<String, Value>> for Metadata {
+    fn from(map: Map<String, Value>) -> Self {
+        Self {
+            map,
+            local_properties: Map::new(),
+        }
     }
 }
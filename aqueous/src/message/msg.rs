@@ -1,9 +1,13 @@
-use super::{Message, MessageData, Metadata};
-use serde::{Deserialize, Serialize};
+use super::{
+    Casing, CopyFields, Format, FormatError, Message, MessageData, Metadata, MsgBuilder, Upcasters,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::error::Error;
+use uuid::Uuid;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Msg<T> {
+    pub id: Uuid,
     pub data: T,
     pub metadata: Metadata,
 }
@@ -12,27 +16,132 @@ impl<T> Msg<T>
 where
     T: Message,
 {
+    /// Starts a new causal chain, rather than following an existing message
+    pub fn build(data: T) -> MsgBuilder<T> {
+        MsgBuilder::new(data)
+    }
+
     pub fn from_data(message_data: MessageData) -> Result<Self, Box<dyn Error>>
     where
         for<'de> T: Deserialize<'de>,
     {
+        let id = message_data.id;
         let data = serde_json::from_value(message_data.data)?;
         let metadata = message_data.metadata;
 
-        Ok(Msg { data, metadata })
+        Ok(Msg { id, data, metadata })
+    }
+
+    pub fn from_cased_data(
+        message_data: MessageData,
+        casing: Casing,
+    ) -> Result<Self, Box<dyn Error>>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        let id = message_data.id;
+        let data = casing.decode(message_data.data)?;
+        let metadata = casing.decode_metadata(message_data.metadata);
+
+        Ok(Msg { id, data, metadata })
+    }
+
+    pub fn upcast(message_data: MessageData, upcasters: &Upcasters) -> Result<Self, Box<dyn Error>>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        Self::upcast_cased(message_data, Casing::default(), upcasters)
+    }
+
+    /// Metadata keys are decoded before upcasting, so the stored schema version
+    /// is found in any casing. Upcasters receive the data in its stored casing.
+    pub fn upcast_cased(
+        message_data: MessageData,
+        casing: Casing,
+        upcasters: &Upcasters,
+    ) -> Result<Self, Box<dyn Error>>
+    where
+        for<'de> T: Deserialize<'de>,
+    {
+        let message_data = MessageData {
+            metadata: casing.decode_metadata(message_data.metadata),
+            ..message_data
+        };
+        let message_data = upcasters.upcast::<T>(message_data)?;
+
+        let id = message_data.id;
+        let data = casing.decode(message_data.data)?;
+        let metadata = message_data.metadata;
+
+        Ok(Msg { id, data, metadata })
+    }
+
+    /// Decodes a message stored in format `F` straight into its data type,
+    /// without the `serde_json::Value` that `MessageData` holds
+    pub fn decode<F>(id: Uuid, metadata: &[u8], data: &[u8]) -> Result<Self, FormatError>
+    where
+        F: Format,
+        T: DeserializeOwned,
+    {
+        let data = F::deserialize(data)?;
+        let metadata = F::deserialize(metadata)?;
+
+        Ok(Msg { id, data, metadata })
+    }
+
+    /// Encodes the message's metadata and data, in that order, to be written
+    /// in format `F`, as `MessageData::try_from` would prepare them
+    pub fn encode<F>(&self) -> Result<(Vec<u8>, Vec<u8>), FormatError>
+    where
+        F: Format,
+        T: Serialize,
+    {
+        let metadata =
+            Metadata::for_write(self.metadata.clone()).set_schema_version(T::SCHEMA_VERSION);
+
+        Ok((F::serialize(&metadata)?, F::serialize(&self.data)?))
     }
 
     pub fn follow<M>(message: Msg<M>) -> Self
     where
         T: From<M>,
     {
+        let id = Uuid::new_v4();
         let data = T::from(message.data);
         let metadata = Metadata::follow(message.metadata);
 
-        Self { data, metadata }
+        Self { id, data, metadata }
+    }
+
+    /// Follows a message whose data shares fields with this message's data
+    pub fn copy<M>(message: Msg<M>, fields: &CopyFields) -> Result<Self, Box<dyn Error>>
+    where
+        M: Serialize,
+        for<'de> T: Deserialize<'de>,
+    {
+        let id = Uuid::new_v4();
+        let data = serde_json::to_value(&message.data)?;
+        let data = serde_json::from_value(fields.apply(data))?;
+        let metadata = Metadata::follow(message.metadata);
+
+        Ok(Self { id, data, metadata })
     }
 
     pub fn message_type(&self) -> &str {
         T::TYPE_NAME
     }
 }
+
+impl<T> Msg<T> {
+    pub fn with_id(mut self, id: Uuid) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Msg<U> {
+        let Self { id, data, metadata } = self;
+        let data = f(data);
+
+        Msg { id, data, metadata }
+    }
+}
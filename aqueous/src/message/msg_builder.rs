@@ -0,0 +1,123 @@
+use super::{Message, Metadata, Msg};
+use crate::stream_name::StreamName;
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum MetadataError {
+    #[error("Metadata keys and property names cannot be empty")]
+    EmptyKey,
+    #[error("{0} is reserved by the message store and cannot be set on a new message")]
+    ReservedKey(String),
+    #[error("Schema version {0} is not a whole number of at least 1")]
+    InvalidSchemaVersion(Value),
+}
+
+pub struct MsgBuilder<T> {
+    id: Uuid,
+    data: T,
+    metadata: Metadata,
+    error: Option<MetadataError>,
+}
+
+impl<T> MsgBuilder<T>
+where
+    T: Message,
+{
+    /// Keys written by the message store, or derived from a causation message
+    pub const RESERVED_KEYS: &'static [&'static str] = &[
+        Metadata::CAUSATION_MESSAGE_STREAM_NAME_KEY,
+        Metadata::CAUSATION_MESSAGE_POSITION_KEY,
+        Metadata::CAUSATION_MESSAGE_GLOBAL_POSITION_KEY,
+        Metadata::POSITION_KEY,
+        Metadata::GLOBAL_POSITION_KEY,
+        Metadata::STREAM_NAME_KEY,
+        Metadata::TIME_KEY,
+        Metadata::REPLAY_STREAM_NAME_KEY,
+        Metadata::PROPERTIES_KEY,
+        Metadata::SCHEMA_VERSION_KEY,
+    ];
+
+    pub fn new(data: T) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            data,
+            metadata: Metadata::new(),
+            error: None,
+        }
+    }
+
+    /// Supplies the message's ID, such as one sent by a client retrying a command
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn correlation_stream_name(mut self, stream_name: StreamName) -> Self {
+        self.metadata = self.metadata.set_correlation_stream_name(stream_name);
+        self
+    }
+
+    pub fn reply_stream_name(mut self, stream_name: StreamName) -> Self {
+        self.metadata = self.metadata.set_reply_stream_name(stream_name);
+        self
+    }
+
+    pub fn set<V>(mut self, key: &str, value: V) -> Self
+    where
+        Value: From<V>,
+    {
+        if key.is_empty() {
+            self.error.get_or_insert(MetadataError::EmptyKey);
+        } else if Self::RESERVED_KEYS.contains(&key) {
+            self.error
+                .get_or_insert(MetadataError::ReservedKey(key.to_owned()));
+        } else {
+            self.metadata = self.metadata.set(key, value);
+        }
+
+        self
+    }
+
+    pub fn property<V>(mut self, name: &str, value: V) -> Self
+    where
+        Value: From<V>,
+    {
+        if name.is_empty() {
+            self.error.get_or_insert(MetadataError::EmptyKey);
+        } else {
+            self.metadata = self.metadata.set_property(name, value);
+        }
+
+        self
+    }
+
+    pub fn local_property<V>(mut self, name: &str, value: V) -> Self
+    where
+        Value: From<V>,
+    {
+        if name.is_empty() {
+            self.error.get_or_insert(MetadataError::EmptyKey);
+        } else {
+            self.metadata = self.metadata.set_local_property(name, value);
+        }
+
+        self
+    }
+
+    /// Fails with the first invalid key that was given
+    pub fn build(self) -> Result<Msg<T>, MetadataError> {
+        let Self {
+            id,
+            data,
+            metadata,
+            error,
+        } = self;
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok(Msg { id, data, metadata }),
+        }
+    }
+}
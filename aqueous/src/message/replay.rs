@@ -0,0 +1,110 @@
+use super::{MessageData, Metadata};
+use crate::{
+    message_store::{MessageStore, Reader, ReaderError},
+    stream_name::StreamName,
+};
+use futures::TryStreamExt;
+use std::pin::pin;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Message {0} has no stream name to record as the replay's origin")]
+    MissingStreamName(Uuid),
+}
+
+#[derive(Debug, Error)]
+pub enum ReplayWriteError<E> {
+    #[error(transparent)]
+    Replay(#[from] ReplayError),
+    #[error(transparent)]
+    Read(#[from] ReaderError<E>),
+    #[error(transparent)]
+    Write(E),
+}
+
+/// Prepares messages read from one stream or category to be written into
+/// another stream, recording where each message was originally written
+#[derive(Clone, Debug)]
+pub struct Replay {
+    stream_name: StreamName,
+}
+
+impl Replay {
+    pub fn new(stream_name: StreamName) -> Self {
+        Self { stream_name }
+    }
+
+    /// The stream that replayed messages are written to
+    pub fn stream_name(&self) -> &StreamName {
+        &self.stream_name
+    }
+
+    /// Fails for messages that were not read from a stream, since the replay
+    /// could not be told apart from the original
+    pub fn message(&self, message_data: MessageData) -> Result<MessageData, ReplayError> {
+        let MessageData {
+            id,
+            type_name,
+            metadata,
+            data,
+        } = message_data;
+
+        // Messages that are replayed again keep the stream they originated from
+        let replay_stream_name = metadata
+            .replay_stream_name()
+            .or_else(|| metadata.stream_name())
+            .ok_or(ReplayError::MissingStreamName(id))?;
+
+        let metadata = Metadata::for_write(metadata).set_replay_stream_name(replay_stream_name);
+
+        // A replayed message is a new message, so it cannot reuse the original's ID
+        Ok(MessageData {
+            id: Uuid::new_v4(),
+            type_name,
+            metadata,
+            data,
+        })
+    }
+
+    pub fn messages<'a, I>(
+        &'a self,
+        messages: I,
+    ) -> impl Iterator<Item = Result<MessageData, ReplayError>> + 'a
+    where
+        I: IntoIterator<Item = MessageData>,
+        I::IntoIter: 'a,
+    {
+        messages
+            .into_iter()
+            .map(|message_data| self.message(message_data))
+    }
+
+    /// Reads every message from the source stream or category and writes it
+    /// into the replay stream in order, returning how many were written
+    pub async fn write<S>(
+        &self,
+        store: &S,
+        source: StreamName,
+    ) -> Result<u64, ReplayWriteError<S::Error>>
+    where
+        S: MessageStore + Sync,
+    {
+        let mut messages = pin!(Reader::new(store, source).read());
+        let mut count = 0;
+
+        while let Some(message_data) = messages.try_next().await? {
+            let message_data = self.message(message_data)?;
+
+            store
+                .write_message(&message_data, &self.stream_name, None)
+                .await
+                .map_err(ReplayWriteError::Write)?;
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
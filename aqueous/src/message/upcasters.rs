@@ -0,0 +1,90 @@
+use super::{Message, MessageData, MetadataError};
+use serde_json::Value;
+use std::collections::HashMap;
+use thiserror::Error;
+
+type Upcast = Box<dyn Fn(Value) -> Value + Send + Sync>;
+
+#[derive(Debug, Error)]
+pub enum UpcastError {
+    #[error("No upcaster registered for {type_name} from schema version {schema_version}")]
+    MissingUpcaster {
+        type_name: &'static str,
+        schema_version: u32,
+    },
+    #[error("{type_name} schema version {schema_version} is newer than {supported_version}")]
+    UnsupportedVersion {
+        type_name: &'static str,
+        schema_version: u32,
+        supported_version: u32,
+    },
+    #[error(transparent)]
+    Metadata(#[from] MetadataError),
+}
+
+/// Transforms the data of older message schemas, one version at a time, into
+/// the shape of the current `Message::SCHEMA_VERSION`
+#[derive(Default)]
+pub struct Upcasters {
+    upcasts: HashMap<(&'static str, u32), Upcast>,
+}
+
+impl Upcasters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the transformation of `M` data from `from_version` to `from_version + 1`
+    pub fn register<M, F>(mut self, from_version: u32, upcast: F) -> Self
+    where
+        M: Message,
+        F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        let key = (M::TYPE_NAME, from_version);
+        self.upcasts.insert(key, Box::new(upcast));
+        self
+    }
+
+    pub fn upcast<M>(&self, message_data: MessageData) -> Result<MessageData, UpcastError>
+    where
+        M: Message,
+    {
+        let MessageData {
+            id,
+            type_name,
+            metadata,
+            mut data,
+        } = message_data;
+
+        let mut schema_version = metadata.schema_version()?;
+
+        if schema_version > M::SCHEMA_VERSION {
+            return Err(UpcastError::UnsupportedVersion {
+                type_name: M::TYPE_NAME,
+                schema_version,
+                supported_version: M::SCHEMA_VERSION,
+            });
+        }
+
+        while schema_version < M::SCHEMA_VERSION {
+            let upcast = self.upcasts.get(&(M::TYPE_NAME, schema_version)).ok_or(
+                UpcastError::MissingUpcaster {
+                    type_name: M::TYPE_NAME,
+                    schema_version,
+                },
+            )?;
+
+            data = upcast(data);
+            schema_version += 1;
+        }
+
+        let metadata = metadata.set_schema_version(schema_version);
+
+        Ok(MessageData {
+            id,
+            type_name,
+            metadata,
+            data,
+        })
+    }
+}
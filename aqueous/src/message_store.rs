@@ -0,0 +1,68 @@
+mod category_filter;
+mod reader;
+mod wakeup;
+
+pub use category_filter::*;
+pub use reader::*;
+pub use wakeup::*;
+
+use crate::{
+    message::MessageData,
+    stream_name::{Category, StreamName},
+};
+use async_trait::async_trait;
+
+/// Reads from and writes to a message store such as Message DB. Read messages
+/// carry their stream name, position, global position and time in their metadata.
+#[async_trait]
+pub trait MessageStore {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Messages from the stream at or after the stream `position`
+    async fn get_stream_messages(
+        &self,
+        stream_name: &StreamName,
+        position: i64,
+        batch_size: i64,
+    ) -> Result<Vec<MessageData>, Self::Error>;
+
+    /// Messages from the category at or after the `global_position` that
+    /// match the filter. Up to `batch_size` matching messages must be returned;
+    /// fewer are only returned once the category has no more, which readers
+    /// take as its end. Backends that filter in-process must keep reading
+    /// until the batch is full rather than filter a page of unfiltered messages.
+    async fn get_category_messages(
+        &self,
+        category: &Category,
+        global_position: i64,
+        batch_size: i64,
+        filter: &CategoryFilter,
+    ) -> Result<Vec<MessageData>, Self::Error>;
+
+    /// The stream's most recent message, or its most recent message of
+    /// `type_name`, such as the latest snapshot or position record
+    async fn get_last_stream_message(
+        &self,
+        stream_name: &StreamName,
+        type_name: Option<&str>,
+    ) -> Result<Option<MessageData>, Self::Error>;
+
+    /// Writes the message to the end of the stream and returns its position.
+    /// Fails when `expected_version` is given and the stream is no longer at
+    /// that version, where `-1` expects the stream to have no messages.
+    async fn write_message(
+        &self,
+        message_data: &MessageData,
+        stream_name: &StreamName,
+        expected_version: Option<i64>,
+    ) -> Result<i64, Self::Error>;
+
+    /// Position of the stream's last message, the expected version for the
+    /// next write, or `None` when the stream has no messages
+    async fn stream_version(&self, stream_name: &StreamName) -> Result<Option<i64>, Self::Error> {
+        let message_data = self.get_last_stream_message(stream_name, None).await?;
+        let version = message_data.and_then(|message_data| message_data.metadata.position());
+
+        Ok(version)
+    }
+}
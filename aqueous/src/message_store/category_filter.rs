@@ -0,0 +1,78 @@
+use crate::message::MessageData;
+use time::PrimitiveDateTime;
+
+/// Optional restrictions on the messages returned by a category read
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CategoryFilter {
+    pub type_names: Vec<String>,
+    pub after: Option<PrimitiveDateTime>,
+    pub before: Option<PrimitiveDateTime>,
+    /// SQL for Message DB's `condition` parameter, where columns of the
+    /// `messages` table can be referenced, e.g. `messages.position = 0`. No
+    /// `MessageStore` in this crate reads it, and `matches` ignores it; it is
+    /// only applied by stores that pass it on to Message DB.
+    pub condition: Option<String>,
+}
+
+impl CategoryFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the read to these types; all types are read by default
+    pub fn type_names<I>(mut self, type_names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: ToString,
+    {
+        let type_names = type_names
+            .into_iter()
+            .map(|type_name| type_name.to_string());
+        self.type_names.extend(type_names);
+        self
+    }
+
+    /// Messages written at or after `time`
+    pub fn after(mut self, time: PrimitiveDateTime) -> Self {
+        self.after = Some(time);
+        self
+    }
+
+    /// Messages written before `time`
+    pub fn before(mut self, time: PrimitiveDateTime) -> Self {
+        self.before = Some(time);
+        self
+    }
+
+    pub fn condition(mut self, condition: impl ToString) -> Self {
+        self.condition = Some(condition.to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Applies the type and time restrictions in-process, for backends that
+    /// cannot apply them when reading. The SQL condition is not evaluated.
+    /// Such backends still owe a full batch of matching messages, as
+    /// `MessageStore::get_category_messages` requires.
+    pub fn matches(&self, message_data: &MessageData) -> bool {
+        if !self.type_names.is_empty() && !self.type_names.contains(&message_data.type_name) {
+            return false;
+        }
+
+        if self.after.is_none() && self.before.is_none() {
+            return true;
+        }
+
+        let Some(time) = message_data.metadata.time() else {
+            return false;
+        };
+
+        let after = self.after.is_none_or(|after| time >= after);
+        let before = self.before.is_none_or(|before| time < before);
+
+        after && before
+    }
+}
@@ -0,0 +1,127 @@
+use super::{CategoryFilter, MessageStore};
+use crate::{message::MessageData, stream_name::StreamName};
+use futures::{stream, Stream, TryStreamExt};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ReaderError<E> {
+    #[error(transparent)]
+    Store(#[from] E),
+    #[error("Message {0} ends a full batch without a position to continue reading from")]
+    MissingPosition(Uuid),
+}
+
+/// Where the next batch is read from
+enum Next {
+    Position(i64),
+    MissingPosition(Uuid),
+    End,
+}
+
+/// Reads a whole stream or category as an async stream of messages, paging
+/// through the message store a batch at a time
+pub struct Reader<'a, S> {
+    store: &'a S,
+    stream_name: StreamName,
+    position: i64,
+    batch_size: i64,
+    filter: CategoryFilter,
+}
+
+impl<'a, S> Reader<'a, S>
+where
+    S: MessageStore + Sync,
+{
+    pub const DEFAULT_BATCH_SIZE: i64 = 1000;
+
+    /// Stream names without an ID, such as `account`, are read as categories
+    pub fn new(store: &'a S, stream_name: StreamName) -> Self {
+        Self {
+            store,
+            stream_name,
+            position: 0,
+            batch_size: Self::DEFAULT_BATCH_SIZE,
+            filter: CategoryFilter::new(),
+        }
+    }
+
+    /// The stream position, or global position for categories, to start from
+    pub fn position(mut self, position: i64) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Applied to category reads only
+    pub fn filter(mut self, filter: CategoryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// A batch smaller than the batch size ends the read. A full batch whose
+    /// last message has no position fails the read once its messages are read.
+    pub fn read(self) -> impl Stream<Item = Result<MessageData, ReaderError<S::Error>>> + 'a {
+        let Self {
+            store,
+            stream_name,
+            position,
+            batch_size,
+            filter,
+        } = self;
+
+        let batches = stream::try_unfold(Next::Position(position), move |next| {
+            let stream_name = stream_name.clone();
+            let filter = filter.clone();
+
+            async move {
+                let position = match next {
+                    Next::Position(position) => position,
+                    Next::MissingPosition(id) => return Err(ReaderError::MissingPosition(id)),
+                    Next::End => return Ok(None),
+                };
+
+                let batch = if stream_name.is_category() {
+                    let category = stream_name.category();
+                    store
+                        .get_category_messages(&category, position, batch_size, &filter)
+                        .await?
+                } else {
+                    store
+                        .get_stream_messages(&stream_name, position, batch_size)
+                        .await?
+                };
+
+                let next = match batch.last() {
+                    Some(message_data) if batch.len() as i64 >= batch_size => {
+                        match next_position(&stream_name, message_data) {
+                            Some(position) => Next::Position(position),
+                            None => Next::MissingPosition(message_data.id),
+                        }
+                    }
+                    _ => Next::End,
+                };
+
+                Ok(Some((batch, next)))
+            }
+        });
+
+        batches
+            .map_ok(|batch| stream::iter(batch.into_iter().map(Ok)))
+            .try_flatten()
+    }
+}
+
+fn next_position(stream_name: &StreamName, message_data: &MessageData) -> Option<i64> {
+    let position = if stream_name.is_category() {
+        message_data.metadata.global_position()?
+    } else {
+        message_data.metadata.position()?
+    };
+
+    Some(position + 1)
+}
@@ -0,0 +1,105 @@
+use crate::stream_name::Category;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Postgres rejects channel names of `NAMEDATALEN` (64) bytes or more
+pub const MAX_CHANNEL_LENGTH: usize = 63;
+
+/// Installs a trigger on Message DB's messages table that notifies the
+/// category's channel, with the message's global position as the payload,
+/// whenever a message is written
+pub const NOTIFY_TRIGGER_SQL: &str = r#"
+CREATE OR REPLACE FUNCTION message_store.notify_message_written() RETURNS trigger AS $$
+DECLARE
+  _category text := message_store.category(NEW.stream_name);
+  _channel text := 'message_store:' || _category;
+BEGIN
+  IF octet_length(_channel) > 63 THEN
+    _channel := 'message_store:' || md5(_category);
+  END IF;
+
+  PERFORM pg_notify(_channel, NEW.global_position::text);
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS notify_message_written ON message_store.messages;
+CREATE TRIGGER notify_message_written AFTER INSERT ON message_store.messages
+  FOR EACH ROW EXECUTE FUNCTION message_store.notify_message_written();
+"#;
+
+/// The channel `NOTIFY_TRIGGER_SQL` notifies for the category. Channels that
+/// would be too long use the MD5 digest of the category instead.
+pub fn notification_channel(category: &Category) -> String {
+    let channel = format!("message_store:{}", category);
+
+    if channel.len() <= MAX_CHANNEL_LENGTH {
+        channel
+    } else {
+        format!("message_store:{:x}", md5::compute(&category.0))
+    }
+}
+
+/// `LISTEN`s on the category's channel. The channel is quoted, since unquoted
+/// identifiers would be folded to lowercase and could not contain `:`.
+pub fn listen_sql(category: &Category) -> String {
+    let channel = notification_channel(category).replace('"', "\"\"");
+    format!("LISTEN \"{}\"", channel)
+}
+
+/// A subscription to notifications that messages were written to a category,
+/// such as a Postgres connection `LISTEN`ing on the category's channel
+#[async_trait]
+pub trait Notifications {
+    /// Resolves with the notified global position, or `None` once the
+    /// subscription is closed
+    async fn notified(&mut self) -> Option<i64>;
+}
+
+/// Waits between a consumer's reads: until a notification arrives, or until
+/// the poll interval elapses for when notifications are missed or unavailable
+pub struct Wakeup<N> {
+    notifications: Option<N>,
+    poll_interval: Duration,
+}
+
+impl<N> Wakeup<N>
+where
+    N: Notifications + Send,
+{
+    pub fn new(notifications: N, poll_interval: Duration) -> Self {
+        Self {
+            notifications: Some(notifications),
+            poll_interval,
+        }
+    }
+
+    /// Polls on the interval alone
+    pub fn polling(poll_interval: Duration) -> Self {
+        Self {
+            notifications: None,
+            poll_interval,
+        }
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.notifications.is_some()
+    }
+
+    pub async fn wait(&mut self) {
+        let Some(notifications) = &mut self.notifications else {
+            tokio::time::sleep(self.poll_interval).await;
+            return;
+        };
+
+        tokio::select! {
+            notified = notifications.notified() => {
+                if notified.is_none() {
+                    tracing::warn!("Notifications closed, falling back to polling");
+                    self.notifications = None;
+                }
+            }
+            _ = tokio::time::sleep(self.poll_interval) => {}
+        }
+    }
+}
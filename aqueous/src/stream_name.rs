@@ -1,14 +1,31 @@
 mod category;
+mod category_pattern;
+mod category_ref;
+mod compound_id;
+mod consumer_group;
+mod error;
 pub mod separator;
+mod stream_name_ref;
 
 pub use category::*;
+pub use category_pattern::*;
+pub use category_ref::*;
+pub use compound_id::*;
+pub use consumer_group::*;
+pub use error::*;
+pub use stream_name_ref::*;
 
+pub use aqueous_macros::category;
+
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::VecDeque,
     fmt::{Display, Formatter},
+    str::FromStr,
 };
+use uuid::Uuid;
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(transparent)]
 pub struct StreamName(pub String);
 
 impl StreamName {
@@ -16,6 +33,59 @@ impl StreamName {
         Self(stream_name.to_string())
     }
 
+    /// Rejects empty categories, category types and IDs, including those left
+    /// by stray separators such as `account-`, `account:-1` or `account-1+`,
+    /// as well as a second category type separator, as in `account::command`,
+    /// and whitespace or control characters anywhere in the stream name
+    pub fn parse(stream_name: &str) -> Result<Self, StreamNameError> {
+        let invalid = || stream_name.to_owned();
+
+        if stream_name.is_empty() {
+            return Err(StreamNameError::Empty);
+        }
+
+        if stream_name
+            .chars()
+            .any(|char| char.is_whitespace() || char.is_control())
+        {
+            return Err(StreamNameError::InvalidCharacter(invalid()));
+        }
+
+        let (category, id) = match stream_name.split_once(separator::ID) {
+            Some((category, id)) => (category, Some(id)),
+            None => (stream_name, None),
+        };
+
+        let (entity_id, category_type) = match category.split_once(separator::CATEGORY_TYPE) {
+            Some((entity_id, category_type)) => (entity_id, Some(category_type)),
+            None => (category, None),
+        };
+
+        if entity_id.is_empty() {
+            return Err(StreamNameError::EmptyCategory(invalid()));
+        }
+
+        if let Some(category_type) = category_type {
+            if category_type.contains(separator::CATEGORY_TYPE) {
+                return Err(StreamNameError::ExtraCategoryTypeSeparator(invalid()));
+            }
+
+            let mut types = category_type.split(separator::COMPOUND);
+            if types.any(str::is_empty) {
+                return Err(StreamNameError::EmptyCategoryType(invalid()));
+            }
+        }
+
+        if let Some(id) = id {
+            let mut ids = id.split(separator::COMPOUND);
+            if ids.any(str::is_empty) {
+                return Err(StreamNameError::EmptyId(invalid()));
+            }
+        }
+
+        Ok(Self::new(stream_name))
+    }
+
     pub fn from_parts(category: Category, id: StreamID) -> Self {
         let Category(category) = category;
         let stream_name = category + separator::ID + id.as_ref();
@@ -23,11 +93,20 @@ impl StreamName {
         StreamName(stream_name)
     }
 
+    pub fn view(&self) -> StreamNameRef<'_> {
+        StreamNameRef::from(self)
+    }
+
     pub fn has_id(&self) -> bool {
         let Self(stream_name) = self;
         stream_name.contains(separator::ID)
     }
 
+    /// Category stream names, such as `account`, have no ID
+    pub fn is_category(&self) -> bool {
+        !self.has_id()
+    }
+
     pub fn category(&self) -> Category {
         let (category, ..) = self.split();
         category
@@ -63,6 +142,15 @@ impl StreamName {
         }
     }
 
+    pub fn compound_id<C: CompoundID>(&self) -> Option<Result<C, CompoundIDError>> {
+        let id = self.id()?;
+        Some(id.to_compound())
+    }
+
+    pub fn consumer_group_member(&self, group_size: i64, hashing: GroupHashing) -> Option<i64> {
+        self.view().consumer_group_member(group_size, hashing)
+    }
+
     pub fn cardinal_id(&self) -> Option<StreamID> {
         let (.., id) = self.split();
         let ids = id?.split();
@@ -71,19 +159,30 @@ impl StreamName {
         Some(id.clone())
     }
 
+    /// Splits on the first ID separator only, so IDs may contain hyphens
     pub fn split(&self) -> (Category, Option<StreamID>) {
         let Self(stream_name) = self;
-        let mut splits = stream_name.split(separator::ID).collect::<VecDeque<_>>();
-        let category = Category::new(splits.pop_front().expect("Category"));
-
-        let id = if !splits.is_empty() {
-            let id = StreamID(splits.make_contiguous().concat());
-            Some(id)
-        } else {
-            None
-        };
 
-        (category, id)
+        match stream_name.split_once(separator::ID) {
+            Some((category, id)) => (Category::new(category), Some(StreamID::new(id))),
+            None => (Category::new(stream_name), None),
+        }
+    }
+}
+
+impl FromStr for StreamName {
+    type Err = StreamNameError;
+
+    fn from_str(stream_name: &str) -> Result<Self, Self::Err> {
+        Self::parse(stream_name)
+    }
+}
+
+impl TryFrom<&str> for StreamName {
+    type Error = StreamNameError;
+
+    fn try_from(stream_name: &str) -> Result<Self, Self::Error> {
+        Self::parse(stream_name)
     }
 }
 
@@ -99,7 +198,8 @@ impl Display for StreamName {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(transparent)]
 pub struct StreamID(pub String);
 
 impl StreamID {
@@ -116,6 +216,10 @@ impl StreamID {
             .collect()
     }
 
+    pub fn as_uuid(&self) -> Option<Uuid> {
+        Uuid::parse_str(&self.0).ok()
+    }
+
     pub fn join(ids: &[Self]) -> Self {
         let joined_ids = ids
             .iter()
@@ -127,6 +231,24 @@ impl StreamID {
     }
 }
 
+impl From<&str> for StreamID {
+    fn from(stream_id: &str) -> Self {
+        Self::new(stream_id)
+    }
+}
+
+impl From<String> for StreamID {
+    fn from(stream_id: String) -> Self {
+        Self(stream_id)
+    }
+}
+
+impl From<Uuid> for StreamID {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid.hyphenated().to_string())
+    }
+}
+
 impl AsRef<str> for StreamID {
     fn as_ref(&self) -> &str {
         self.0.as_ref()
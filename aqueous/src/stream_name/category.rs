@@ -1,10 +1,9 @@
-use super::{separator, StreamID, StreamName};
-use std::{
-    collections::VecDeque,
-    fmt::{Display, Formatter},
-};
+use super::{separator, CategoryRef, StreamID, StreamName};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(transparent)]
 pub struct Category(pub String);
 
 impl Category {
@@ -12,6 +11,10 @@ impl Category {
         Self(category.to_string())
     }
 
+    pub fn view(&self) -> CategoryRef<'_> {
+        CategoryRef::from(self)
+    }
+
     pub fn from_parts(entity_id: EntityID, category_type: CategoryType) -> Self {
         let EntityID(entity_id) = entity_id;
         let CategoryType(category_type) = category_type;
@@ -21,29 +24,34 @@ impl Category {
     }
 
     pub fn new_command(category: impl ToString) -> Self {
-        let category_type = CategoryType::new("command");
+        let category_type = CategoryType::new(CategoryType::COMMAND);
 
         Self::new(category).add_type(category_type)
     }
 
-    pub fn split(&self) -> (EntityID, Option<CategoryType>) {
-        let Self(category) = self;
-        let mut splits = category
-            .split(separator::CATEGORY_TYPE)
-            .collect::<VecDeque<_>>();
-        let id_str = splits.pop_front().expect("EntityID");
-        let entity_id = EntityID::new(id_str);
+    pub fn new_position(category: impl ToString) -> Self {
+        let category_type = CategoryType::new(CategoryType::POSITION);
 
-        let category_type = if !splits.is_empty() {
-            let type_string = splits.make_contiguous().concat();
-            let category_type = CategoryType(type_string);
+        Self::new(category).add_type(category_type)
+    }
 
-            Some(category_type)
-        } else {
-            None
-        };
+    pub fn new_snapshot(category: impl ToString) -> Self {
+        let category_type = CategoryType::new(CategoryType::SNAPSHOT);
 
-        (entity_id, category_type)
+        Self::new(category).add_type(category_type)
+    }
+
+    /// Splits on the first category type separator only
+    pub fn split(&self) -> (EntityID, Option<CategoryType>) {
+        let Self(category) = self;
+
+        match category.split_once(separator::CATEGORY_TYPE) {
+            Some((entity_id, category_type)) => {
+                let category_type = CategoryType::new(category_type);
+                (EntityID::new(entity_id), Some(category_type))
+            }
+            None => (EntityID::new(category), None),
+        }
     }
 
     pub fn entity_id(&self) -> EntityID {
@@ -65,12 +73,30 @@ impl Category {
         }
     }
 
-    pub fn has_type(&self) -> bool {
+    /// Whether the category has any category type. This was `has_type()`
+    /// before `has_type` took the category type to look for.
+    pub fn has_types(&self) -> bool {
         let Category(category) = self;
 
         category.contains(separator::CATEGORY_TYPE)
     }
 
+    /// Whether `category_type` is one of the category's types, as in
+    /// `account:command+position`
+    pub fn has_type(&self, category_type: &CategoryType) -> bool {
+        self.view().has_type(category_type.as_ref())
+    }
+
+    pub fn is_command(&self) -> bool {
+        self.view().is_command()
+    }
+
+    /// Whether the stream name is in this category, either as an entity stream
+    /// or as the category itself
+    pub fn matches(&self, stream_name: &StreamName) -> bool {
+        stream_name.view().category().as_ref() == self.0
+    }
+
     pub fn add_type(&self, new_type: CategoryType) -> Self {
         let (EntityID(entity_id), category_type) = self.split();
 
@@ -109,7 +135,8 @@ impl Display for Category {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(transparent)]
 pub struct EntityID(pub String);
 
 impl EntityID {
@@ -130,10 +157,15 @@ impl Display for EntityID {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(transparent)]
 pub struct CategoryType(pub String);
 
 impl CategoryType {
+    pub const COMMAND: &'static str = "command";
+    pub const POSITION: &'static str = "position";
+    pub const SNAPSHOT: &'static str = "snapshot";
+
     pub fn new(category_type: impl ToString) -> Self {
         Self(category_type.to_string())
     }
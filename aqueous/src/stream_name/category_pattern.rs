@@ -0,0 +1,52 @@
+use super::{Category, StreamName};
+
+/// Glob-style pattern over categories, where `*` matches any run of characters
+/// and `?` matches a single character, e.g. `account*` or `*:command`
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CategoryPattern(pub String);
+
+impl CategoryPattern {
+    pub fn new(pattern: impl ToString) -> Self {
+        Self(pattern.to_string())
+    }
+
+    pub fn matches(&self, category: &Category) -> bool {
+        glob_match(&self.0, category.as_ref())
+    }
+
+    pub fn matches_stream_name(&self, stream_name: &StreamName) -> bool {
+        glob_match(&self.0, stream_name.view().category().as_ref())
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it is currently matched up to
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, star_t)) => {
+                    backtrack = Some((star, star_t + 1));
+                    p = star + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,71 @@
+use super::{separator, Category, CategoryType};
+use std::fmt::{Display, Formatter};
+
+/// Borrowed view of a category whose accessors return slices of it
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CategoryRef<'a>(pub &'a str);
+
+impl<'a> CategoryRef<'a> {
+    pub fn new(category: &'a str) -> Self {
+        Self(category)
+    }
+
+    pub fn split(&self) -> (&'a str, Option<&'a str>) {
+        let Self(category) = *self;
+
+        match category.split_once(separator::CATEGORY_TYPE) {
+            Some((entity_id, category_type)) => (entity_id, Some(category_type)),
+            None => (category, None),
+        }
+    }
+
+    pub fn entity_id(&self) -> &'a str {
+        let (entity_id, ..) = self.split();
+        entity_id
+    }
+
+    pub fn category_type(&self) -> Option<&'a str> {
+        let (.., category_type) = self.split();
+        category_type
+    }
+
+    pub fn category_types(&self) -> impl Iterator<Item = &'a str> {
+        self.category_type()
+            .into_iter()
+            .flat_map(|category_type| category_type.split(separator::COMPOUND))
+    }
+
+    pub fn has_types(&self) -> bool {
+        self.0.contains(separator::CATEGORY_TYPE)
+    }
+
+    pub fn has_type(&self, category_type: &str) -> bool {
+        self.category_types().any(|other| other == category_type)
+    }
+
+    pub fn is_command(&self) -> bool {
+        self.has_type(CategoryType::COMMAND)
+    }
+
+    pub fn to_category(&self) -> Category {
+        Category::new(self.0)
+    }
+}
+
+impl<'a> From<&'a Category> for CategoryRef<'a> {
+    fn from(category: &'a Category) -> Self {
+        Self(category.as_ref())
+    }
+}
+
+impl AsRef<str> for CategoryRef<'_> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl Display for CategoryRef<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
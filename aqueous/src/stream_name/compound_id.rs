@@ -0,0 +1,53 @@
+use super::{CompoundIDError, StreamID};
+use aqueous_macros::all_tuples;
+use std::str::FromStr;
+
+/// Tuples of typed IDs that make up a compound stream ID, like the
+/// `{account}+{transaction}` in `transfer-{account}+{transaction}`
+pub trait CompoundID: Sized {
+    fn to_ids(&self) -> Vec<StreamID>;
+
+    fn from_ids(ids: &[StreamID]) -> Result<Self, CompoundIDError>;
+}
+
+macro_rules! impl_compound_id {
+    () => {};
+    ($($ty:ident $(,)?)*) => {
+        #[allow(non_snake_case)]
+        impl<$($ty,)*> CompoundID for ($($ty,)*)
+        where
+            $($ty: ToString + FromStr,)*
+        {
+            fn to_ids(&self) -> Vec<StreamID> {
+                let ($($ty,)*) = self;
+                vec![$(StreamID($ty.to_string()),)*]
+            }
+
+            fn from_ids(ids: &[StreamID]) -> Result<Self, CompoundIDError> {
+                let [$($ty,)*] = ids else {
+                    return Err(CompoundIDError::Arity {
+                        expected: [$(stringify!($ty),)*].len(),
+                        actual: ids.len(),
+                    });
+                };
+
+                Ok(($(
+                    $ty.as_ref()
+                        .parse()
+                        .map_err(|_| CompoundIDError::Parse($ty.clone()))?,
+                )*))
+            }
+        }
+    };
+}
+all_tuples!(impl_compound_id, T);
+
+impl StreamID {
+    pub fn from_compound<C: CompoundID>(compound_id: &C) -> Self {
+        Self::join(&compound_id.to_ids())
+    }
+
+    pub fn to_compound<C: CompoundID>(&self) -> Result<C, CompoundIDError> {
+        C::from_ids(&self.split())
+    }
+}
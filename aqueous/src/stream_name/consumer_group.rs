@@ -0,0 +1,39 @@
+use super::StreamNameRef;
+
+/// Which part of a stream's ID assigns it to a consumer group member
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum GroupHashing {
+    /// The first ID of a compound ID, so that all of an entity's streams are
+    /// handled by the same member, as Message DB does
+    #[default]
+    CardinalId,
+    /// The whole compound ID, spreading an entity's streams across members
+    CompoundId,
+}
+
+impl<'a> StreamNameRef<'a> {
+    /// The consumer group member that handles this stream, or `None` for
+    /// stream names without an ID and for group sizes less than 1
+    pub fn consumer_group_member(&self, group_size: i64, hashing: GroupHashing) -> Option<i64> {
+        if group_size < 1 {
+            return None;
+        }
+
+        let id = match hashing {
+            GroupHashing::CardinalId => self.cardinal_id()?,
+            GroupHashing::CompoundId => self.id()?,
+        };
+
+        let member = hash_64(id).unsigned_abs() % group_size as u64;
+        Some(member as i64)
+    }
+}
+
+/// Message DB's `hash_64`: the first 64 bits of the value's MD5 digest
+pub fn hash_64(value: &str) -> i64 {
+    let digest = md5::compute(value);
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&digest[..8]);
+
+    i64::from_be_bytes(bytes)
+}
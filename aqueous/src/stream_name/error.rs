@@ -0,0 +1,26 @@
+use super::StreamID;
+use thiserror::Error;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum StreamNameError {
+    #[error("Stream name is empty")]
+    Empty,
+    #[error("Stream name {0:?} contains whitespace or control characters")]
+    InvalidCharacter(String),
+    #[error("Stream name {0:?} has an empty category")]
+    EmptyCategory(String),
+    #[error("Stream name {0:?} has an empty category type")]
+    EmptyCategoryType(String),
+    #[error("Stream name {0:?} has more than one category type separator")]
+    ExtraCategoryTypeSeparator(String),
+    #[error("Stream name {0:?} has an empty ID")]
+    EmptyId(String),
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum CompoundIDError {
+    #[error("Expected {expected} IDs in the compound ID, but there are {actual}")]
+    Arity { expected: usize, actual: usize },
+    #[error("Could not parse ID {0}")]
+    Parse(StreamID),
+}
@@ -0,0 +1,71 @@
+use super::{separator, CategoryRef, StreamName};
+use std::fmt::{Display, Formatter};
+
+/// Borrowed view of a stream name whose accessors return slices of it
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StreamNameRef<'a>(pub &'a str);
+
+impl<'a> StreamNameRef<'a> {
+    pub fn new(stream_name: &'a str) -> Self {
+        Self(stream_name)
+    }
+
+    pub fn has_id(&self) -> bool {
+        self.0.contains(separator::ID)
+    }
+
+    pub fn is_category(&self) -> bool {
+        !self.has_id()
+    }
+
+    pub fn category(&self) -> CategoryRef<'a> {
+        let (category, ..) = self.split();
+        category
+    }
+
+    pub fn id(&self) -> Option<&'a str> {
+        let (.., id) = self.split();
+        id
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &'a str> {
+        self.id()
+            .into_iter()
+            .flat_map(|id| id.split(separator::COMPOUND))
+    }
+
+    pub fn cardinal_id(&self) -> Option<&'a str> {
+        self.ids().next()
+    }
+
+    pub fn split(&self) -> (CategoryRef<'a>, Option<&'a str>) {
+        let Self(stream_name) = *self;
+
+        match stream_name.split_once(separator::ID) {
+            Some((category, id)) => (CategoryRef(category), Some(id)),
+            None => (CategoryRef(stream_name), None),
+        }
+    }
+
+    pub fn to_stream_name(&self) -> StreamName {
+        StreamName::new(self.0)
+    }
+}
+
+impl<'a> From<&'a StreamName> for StreamNameRef<'a> {
+    fn from(stream_name: &'a StreamName) -> Self {
+        Self(stream_name.as_ref())
+    }
+}
+
+impl AsRef<str> for StreamNameRef<'_> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl Display for StreamNameRef<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
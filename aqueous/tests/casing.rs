@@ -0,0 +1,263 @@
+use aqueous::{
+    message::{Casing, Message, MessageData, Metadata, Msg, Upcasters},
+    stream_name::StreamName,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Deserialize, Message, PartialEq, Serialize)]
+struct Registered {
+    customer_id: String,
+    line_1: String,
+    _tag: Option<String>,
+    mailing_address: Address,
+    balances_by_currency: HashMap<String, i64>,
+    visits_by_year: BTreeMap<u32, u32>,
+    channel: Channel,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct Address {
+    postal_code: String,
+    country_code: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+enum Channel {
+    Web,
+    Branch { branch_id: u32 },
+    Partner(Address),
+}
+
+fn registered() -> Registered {
+    Registered {
+        customer_id: String::from("123"),
+        line_1: String::from("1 Main St"),
+        _tag: Some(String::from("vip")),
+        mailing_address: Address {
+            postal_code: String::from("90210"),
+            country_code: String::from("US"),
+        },
+        balances_by_currency: HashMap::from([
+            (String::from("usd_cents"), 100),
+            (String::from("eurCents"), 200),
+        ]),
+        visits_by_year: BTreeMap::from([(2022, 3), (2023, 5)]),
+        channel: Channel::Branch { branch_id: 7 },
+    }
+}
+
+fn round_trip(registered: Registered, casing: Casing) -> MessageData {
+    let msg = Msg::build(registered.clone()).build().unwrap();
+    let message_data = MessageData::try_from_cased(msg, casing).unwrap();
+
+    let msg = Msg::<Registered>::from_cased_data(message_data.clone(), casing).unwrap();
+    assert_eq!(msg.data, registered);
+
+    message_data
+}
+
+#[test]
+fn every_casing_round_trips() {
+    for casing in [Casing::Snake, Casing::Camel, Casing::Pascal, Casing::Kebab] {
+        round_trip(registered(), casing);
+
+        let partner = Registered {
+            channel: Channel::Partner(registered().mailing_address),
+            ..registered()
+        };
+        round_trip(partner, casing);
+
+        let web = Registered {
+            _tag: None,
+            channel: Channel::Web,
+            ..registered()
+        };
+        round_trip(web, casing);
+    }
+}
+
+#[test]
+fn only_struct_fields_are_converted() {
+    let message_data = round_trip(registered(), Casing::Camel);
+
+    assert_eq!(
+        message_data.data,
+        json!({
+            "customerId": "123",
+            "line1": "1 Main St",
+            "tag": "vip",
+            "mailingAddress": { "postalCode": "90210", "countryCode": "US" },
+            "balancesByCurrency": { "usd_cents": 100, "eurCents": 200 },
+            "visitsByYear": { "2022": 3, "2023": 5 },
+            "channel": { "Branch": { "branchId": 7 } },
+        })
+    );
+}
+
+#[test]
+fn pascal_and_kebab_case_keys() {
+    let message_data = round_trip(registered(), Casing::Pascal);
+    assert_eq!(message_data.data["Line1"], "1 Main St");
+    assert_eq!(message_data.data["Tag"], "vip");
+    assert_eq!(message_data.data["MailingAddress"]["PostalCode"], "90210");
+
+    let message_data = round_trip(registered(), Casing::Kebab);
+    assert_eq!(message_data.data["line-1"], "1 Main St");
+    assert_eq!(message_data.data["-tag"], "vip");
+    assert_eq!(message_data.data["mailing-address"]["postal-code"], "90210");
+}
+
+#[derive(Debug, Deserialize, Message, Serialize)]
+struct Ambiguous {
+    line_1: String,
+    line1: String,
+}
+
+#[test]
+fn fields_that_convert_to_the_same_key_are_rejected() {
+    let ambiguous = Ambiguous {
+        line_1: String::from("1 Main St"),
+        line1: String::from("2 Main St"),
+    };
+    let msg = Msg::build(ambiguous).build().unwrap();
+
+    assert!(MessageData::try_from_cased(msg, Casing::Camel).is_err());
+}
+
+#[test]
+fn metadata_keys_are_converted_but_not_application_keys() {
+    let stream_name = StreamName::new("account-123");
+    let metadata = Metadata::new()
+        .set_correlation_stream_name(stream_name.clone())
+        .set("custom_key", "value");
+
+    let msg = Msg::build(registered()).build().unwrap();
+    let msg = Msg {
+        metadata: metadata.set_property("property_name", "value"),
+        ..msg
+    };
+
+    let message_data = MessageData::try_from_cased(msg, Casing::Camel).unwrap();
+    let map = &message_data.metadata.map;
+
+    assert_eq!(map["correlationStreamName"], "account-123");
+    assert_eq!(map["schemaVersion"], 1);
+    assert_eq!(map["custom_key"], "value");
+    assert_eq!(map["properties"]["property_name"], "value");
+
+    let msg = Msg::<Registered>::from_cased_data(message_data, Casing::Camel).unwrap();
+
+    assert_eq!(msg.metadata.correlation_stream_name(), Some(stream_name));
+    assert_eq!(
+        msg.metadata.get_as::<String>("custom_key").unwrap(),
+        "value"
+    );
+    assert_eq!(
+        msg.metadata
+            .get_property::<String>("property_name")
+            .unwrap(),
+        "value"
+    );
+}
+
+#[derive(Debug, Deserialize, Message, PartialEq, Serialize)]
+struct Deposited {
+    account_id: String,
+    amount_cents: i64,
+}
+
+#[test]
+fn camel_case_data_from_other_services_is_decoded() {
+    let message_data = MessageData {
+        id: Uuid::nil(),
+        type_name: String::from("Deposited"),
+        metadata: Metadata::from(
+            json!({ "schemaVersion": 1, "correlationStreamName": "account-123" })
+                .as_object()
+                .unwrap()
+                .clone(),
+        ),
+        data: json!({ "accountId": "123", "amountCents": 1100 }),
+    };
+
+    let msg =
+        Msg::<Deposited>::upcast_cased(message_data, Casing::Camel, &Upcasters::new()).unwrap();
+
+    assert_eq!(
+        msg.data,
+        Deposited {
+            account_id: String::from("123"),
+            amount_cents: 1100,
+        }
+    );
+    assert_eq!(msg.metadata.schema_version().unwrap(), 1);
+    assert_eq!(
+        msg.metadata.correlation_stream_name(),
+        Some(StreamName::new("account-123"))
+    );
+}
+
+#[derive(Debug, Deserialize, Message, Serialize)]
+struct Paid {
+    payment: Payment,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind")]
+enum Payment {
+    Card { card_number: String },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum Reference {
+    Account { account_id: String },
+}
+
+#[test]
+fn tagged_and_untagged_enums_are_rejected_when_decoding() {
+    let error = Casing::Camel
+        .decode::<Paid>(json!({ "payment": { "kind": "Card", "cardNumber": "4111" } }))
+        .unwrap_err();
+    assert!(error.to_string().contains("tagged and untagged enums"));
+
+    let error = Casing::Camel
+        .decode::<Reference>(json!({ "accountId": "123" }))
+        .unwrap_err();
+    assert!(error.to_string().contains("tagged and untagged enums"));
+
+    // Snake case is decoded by serde_json as it is
+    let paid = Casing::Snake
+        .decode::<Paid>(json!({ "payment": { "kind": "Card", "card_number": "4111" } }))
+        .unwrap();
+    assert!(matches!(paid.payment, Payment::Card { card_number } if card_number == "4111"));
+}
+
+#[derive(Debug, Deserialize, Message, Serialize)]
+struct Transferred {
+    transfer_id: String,
+    #[serde(flatten)]
+    deposited: Deposited,
+}
+
+#[test]
+fn flattened_fields_are_rejected() {
+    let transferred = Transferred {
+        transfer_id: String::from("1"),
+        deposited: Deposited {
+            account_id: String::from("123"),
+            amount_cents: 1100,
+        },
+    };
+
+    let error = Casing::Camel.encode(&transferred).unwrap_err();
+    assert!(error.to_string().contains("flattened fields"));
+
+    let error = Casing::Camel
+        .decode::<Transferred>(json!({ "transferId": "1", "accountId": "123", "amountCents": 1 }))
+        .unwrap_err();
+    assert!(error.to_string().contains("flattened fields"));
+}
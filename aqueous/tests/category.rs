@@ -0,0 +1,54 @@
+use aqueous::stream_name::{category, Category, StreamName};
+use uuid::Uuid;
+
+category!(pub Account, "account");
+
+#[test]
+fn declared_categories_name_their_streams() {
+    assert_eq!(Account::CATEGORY, "account");
+    assert_eq!(Account::category(), Category::new("account"));
+
+    assert_eq!(Account::stream_name("123"), StreamName::new("account-123"));
+    assert_eq!(
+        Account::command_stream_name("123"),
+        StreamName::new("account:command-123")
+    );
+    assert_eq!(
+        Account::position_stream_name("consumer"),
+        StreamName::new("account:position-consumer")
+    );
+    assert_eq!(
+        Account::snapshot_stream_name("123"),
+        StreamName::new("account:snapshot-123")
+    );
+
+    let id = Uuid::nil();
+    assert_eq!(
+        Account::stream_name(id),
+        StreamName::new(format!("account-{id}"))
+    );
+}
+
+#[test]
+fn declared_stream_names_parse() {
+    for stream_name in [
+        Account::stream_name("123"),
+        Account::command_stream_name("123"),
+        Account::position_stream_name("consumer"),
+        Account::snapshot_stream_name("123"),
+    ] {
+        assert_eq!(
+            StreamName::parse(stream_name.as_ref()).unwrap(),
+            stream_name
+        );
+    }
+}
+
+#[test]
+fn declared_categories_are_their_streams_categories() {
+    assert!(Account::category().matches(&Account::stream_name("123")));
+    assert!(Account::command_category().matches(&Account::command_stream_name("123")));
+    assert!(Account::command_category().is_command());
+    assert!(Account::position_category().matches(&Account::position_stream_name("consumer")));
+    assert!(Account::snapshot_category().matches(&Account::snapshot_stream_name("123")));
+}
@@ -0,0 +1,91 @@
+use aqueous::{
+    message::{MessageData, Metadata},
+    message_store::CategoryFilter,
+};
+use serde_json::json;
+use time::{Date, Month, PrimitiveDateTime, Time};
+use uuid::Uuid;
+
+fn at(hour: u8) -> PrimitiveDateTime {
+    let date = Date::from_calendar_date(2023, Month::February, 3).unwrap();
+
+    PrimitiveDateTime::new(date, Time::from_hms(hour, 0, 0).unwrap())
+}
+
+fn written(type_name: &str, time: Option<PrimitiveDateTime>) -> MessageData {
+    let metadata = match time {
+        Some(time) => Metadata::new().set_time(time),
+        None => Metadata::new(),
+    };
+
+    MessageData {
+        id: Uuid::nil(),
+        type_name: type_name.to_owned(),
+        metadata,
+        data: json!({}),
+    }
+}
+
+#[test]
+fn empty_filters_match_every_message() {
+    let filter = CategoryFilter::new();
+
+    assert!(filter.is_empty());
+    assert!(filter.matches(&written("Deposited", None)));
+    assert!(filter.matches(&written("Withdrawn", Some(at(4)))));
+}
+
+#[test]
+fn after_includes_messages_written_at_the_time() {
+    let filter = CategoryFilter::new().after(at(4));
+
+    assert!(!filter.matches(&written("Deposited", Some(at(3)))));
+    assert!(filter.matches(&written("Deposited", Some(at(4)))));
+    assert!(filter.matches(&written("Deposited", Some(at(5)))));
+}
+
+#[test]
+fn before_excludes_messages_written_at_the_time() {
+    let filter = CategoryFilter::new().before(at(4));
+
+    assert!(filter.matches(&written("Deposited", Some(at(3)))));
+    assert!(!filter.matches(&written("Deposited", Some(at(4)))));
+    assert!(!filter.matches(&written("Deposited", Some(at(5)))));
+}
+
+#[test]
+fn after_and_before_bound_a_window() {
+    let filter = CategoryFilter::new().after(at(4)).before(at(6));
+
+    assert!(!filter.matches(&written("Deposited", Some(at(3)))));
+    assert!(filter.matches(&written("Deposited", Some(at(4)))));
+    assert!(filter.matches(&written("Deposited", Some(at(5)))));
+    assert!(!filter.matches(&written("Deposited", Some(at(6)))));
+}
+
+#[test]
+fn time_filters_exclude_messages_without_a_time() {
+    assert!(!CategoryFilter::new()
+        .after(at(4))
+        .matches(&written("Deposited", None)));
+    assert!(!CategoryFilter::new()
+        .before(at(4))
+        .matches(&written("Deposited", None)));
+}
+
+#[test]
+fn type_names_and_times_must_both_match() {
+    let filter = CategoryFilter::new().type_names(["Deposited"]).after(at(4));
+
+    assert!(filter.matches(&written("Deposited", Some(at(5)))));
+    assert!(!filter.matches(&written("Withdrawn", Some(at(5)))));
+    assert!(!filter.matches(&written("Deposited", Some(at(3)))));
+}
+
+#[test]
+fn conditions_are_not_evaluated_in_process() {
+    let filter = CategoryFilter::new().condition("messages.position = 0");
+
+    assert!(!filter.is_empty());
+    assert!(filter.matches(&written("Deposited", None)));
+}
@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+
+use aqueous::{
+    message::{MessageData, Metadata},
+    message_store::{CategoryFilter, MessageStore},
+    stream_name::{Category, StreamName},
+};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Mutex;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+#[error("Expected stream version {expected}, but the stream is at {actual}")]
+pub struct ExpectedVersionError {
+    pub expected: i64,
+    pub actual: i64,
+}
+
+/// Messages in global position order, as Message DB stores them
+#[derive(Default)]
+pub struct InMemoryStore {
+    pub messages: Mutex<Vec<MessageData>>,
+}
+
+impl InMemoryStore {
+    pub fn write(self, stream_name: &str, type_name: &str) -> Self {
+        let message_data = MessageData {
+            id: Uuid::new_v4(),
+            type_name: type_name.to_owned(),
+            metadata: Metadata::new(),
+            data: json!({}),
+        };
+
+        self.append(message_data, &StreamName::new(stream_name));
+        self
+    }
+
+    pub fn write_many(self, stream_name: &str, count: usize) -> Self {
+        (0..count).fold(self, |store, _| store.write(stream_name, "Deposited"))
+    }
+
+    pub fn stream(&self, stream_name: &str) -> Vec<MessageData> {
+        let stream_name = StreamName::new(stream_name);
+
+        self.messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|message_data| {
+                message_data.metadata.stream_name().as_ref() == Some(&stream_name)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn version(messages: &[MessageData], stream_name: &StreamName) -> i64 {
+        let count = messages
+            .iter()
+            .filter(|message_data| {
+                message_data.metadata.stream_name().as_ref() == Some(stream_name)
+            })
+            .count();
+
+        count as i64 - 1
+    }
+
+    fn append(&self, message_data: MessageData, stream_name: &StreamName) -> i64 {
+        let mut messages = self.messages.lock().unwrap();
+        let position = Self::version(&messages, stream_name) + 1;
+        let global_position = messages.len() as i64 + 1;
+
+        let metadata = message_data
+            .metadata
+            .set_stream_name(stream_name.clone())
+            .set_position(position)
+            .set_global_position(global_position);
+
+        messages.push(MessageData {
+            metadata,
+            ..message_data
+        });
+
+        position
+    }
+}
+
+#[async_trait]
+impl MessageStore for InMemoryStore {
+    type Error = ExpectedVersionError;
+
+    async fn get_stream_messages(
+        &self,
+        stream_name: &StreamName,
+        position: i64,
+        batch_size: i64,
+    ) -> Result<Vec<MessageData>, ExpectedVersionError> {
+        let messages = self
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|message_data| {
+                message_data.metadata.stream_name().as_ref() == Some(stream_name)
+            })
+            .filter(|message_data| {
+                let message_position = message_data.metadata.position();
+                message_position.is_none_or(|message_position| message_position >= position)
+            })
+            .take(batch_size as usize)
+            .cloned()
+            .collect();
+
+        Ok(messages)
+    }
+
+    async fn get_category_messages(
+        &self,
+        category: &Category,
+        global_position: i64,
+        batch_size: i64,
+        filter: &CategoryFilter,
+    ) -> Result<Vec<MessageData>, ExpectedVersionError> {
+        let messages = self
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|message_data| category.matches(&message_data.metadata.stream_name().unwrap()))
+            .filter(|message_data| {
+                message_data.metadata.global_position().unwrap() >= global_position
+            })
+            .filter(|message_data| filter.matches(message_data))
+            .take(batch_size as usize)
+            .cloned()
+            .collect();
+
+        Ok(messages)
+    }
+
+    async fn get_last_stream_message(
+        &self,
+        stream_name: &StreamName,
+        type_name: Option<&str>,
+    ) -> Result<Option<MessageData>, ExpectedVersionError> {
+        let message_data = self
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|message_data| {
+                message_data.metadata.stream_name().as_ref() == Some(stream_name)
+            })
+            .find(|message_data| {
+                type_name.is_none_or(|type_name| message_data.type_name == type_name)
+            })
+            .cloned();
+
+        Ok(message_data)
+    }
+
+    async fn write_message(
+        &self,
+        message_data: &MessageData,
+        stream_name: &StreamName,
+        expected_version: Option<i64>,
+    ) -> Result<i64, ExpectedVersionError> {
+        if let Some(expected) = expected_version {
+            let actual = Self::version(&self.messages.lock().unwrap(), stream_name);
+
+            if expected != actual {
+                return Err(ExpectedVersionError { expected, actual });
+            }
+        }
+
+        Ok(self.append(message_data.clone(), stream_name))
+    }
+}
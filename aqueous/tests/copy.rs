@@ -0,0 +1,177 @@
+use aqueous::{
+    message::{CopyFields, CopyFrom, Message, Msg},
+    stream_name::StreamName,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+fn data() -> serde_json::Value {
+    json!({ "account_id": "123", "amount_cents": 1100, "fee_cents": 25 })
+}
+
+#[test]
+fn all_fields_are_copied_by_default() {
+    assert_eq!(CopyFields::new().apply(data()), data());
+}
+
+#[test]
+fn included_fields_are_the_only_fields_copied() {
+    let fields = CopyFields::new()
+        .include(["account_id"])
+        .include(["amount_cents"]);
+
+    assert_eq!(
+        fields.apply(data()),
+        json!({ "account_id": "123", "amount_cents": 1100 })
+    );
+}
+
+#[test]
+fn excluded_fields_are_not_copied() {
+    let fields = CopyFields::new()
+        .include(["account_id", "fee_cents"])
+        .exclude(["fee_cents"]);
+
+    assert_eq!(fields.apply(data()), json!({ "account_id": "123" }));
+}
+
+#[test]
+fn renamed_fields_are_copied_under_their_new_name() {
+    let fields = CopyFields::new()
+        .include(["account_id", "amount_cents"])
+        .rename("amount_cents", "deposit_cents");
+
+    assert_eq!(
+        fields.apply(data()),
+        json!({ "account_id": "123", "deposit_cents": 1100 })
+    );
+}
+
+#[test]
+fn data_other_than_objects_is_copied_as_it_is() {
+    assert_eq!(
+        CopyFields::new().exclude(["a"]).apply(json!([1])),
+        json!([1])
+    );
+}
+
+#[derive(Debug, Deserialize, Message, Serialize)]
+struct Deposit {
+    account_id: String,
+    amount_cents: i64,
+    fee_cents: i64,
+}
+
+#[derive(Debug, Deserialize, Message, Serialize)]
+struct Withdraw {
+    account_id: String,
+    amount_cents: i64,
+    fee_cents: i64,
+}
+
+#[derive(CopyFrom, Debug, Deserialize, Message, PartialEq, Serialize)]
+#[copy(from = Deposit)]
+#[copy(from = Withdraw, exclude(fee_cents))]
+struct Transacted {
+    account_id: String,
+    #[copy(rename = "amount_cents")]
+    total_cents: i64,
+    fee_cents: i64,
+    #[copy(skip)]
+    note: Option<String>,
+}
+
+#[derive(CopyFrom, Debug, Deserialize, Message, PartialEq, Serialize)]
+#[copy(from = Deposit, include(account_id))]
+struct Touched {
+    account_id: String,
+    amount_cents: i64,
+}
+
+fn deposit() -> Deposit {
+    Deposit {
+        account_id: String::from("123"),
+        amount_cents: 1100,
+        fee_cents: 25,
+    }
+}
+
+#[test]
+fn derived_copies_rename_and_skip_fields() {
+    assert_eq!(
+        Transacted::from(deposit()),
+        Transacted {
+            account_id: String::from("123"),
+            total_cents: 1100,
+            fee_cents: 25,
+            note: None,
+        }
+    );
+}
+
+#[test]
+fn derived_copies_select_fields_per_source() {
+    let withdraw = Withdraw {
+        account_id: String::from("456"),
+        amount_cents: 500,
+        fee_cents: 10,
+    };
+
+    assert_eq!(
+        Transacted::from(withdraw),
+        Transacted {
+            account_id: String::from("456"),
+            total_cents: 500,
+            fee_cents: 0,
+            note: None,
+        }
+    );
+
+    assert_eq!(
+        Touched::from(deposit()),
+        Touched {
+            account_id: String::from("123"),
+            amount_cents: 0,
+        }
+    );
+}
+
+#[test]
+fn derived_copies_follow_the_source_message() {
+    let msg = Msg::build(deposit()).build().unwrap();
+    let msg = Msg {
+        metadata: msg.metadata.set_stream_name(StreamName::new("account-123")),
+        ..msg
+    };
+
+    let transacted = Msg::<Transacted>::follow(msg);
+
+    assert_eq!(transacted.data.total_cents, 1100);
+    assert_eq!(
+        transacted.metadata.causation_message_stream_name(),
+        Some(StreamName::new("account-123"))
+    );
+}
+
+#[test]
+fn copied_messages_follow_the_source_message() {
+    let msg = Msg::build(deposit()).build().unwrap();
+    let fields = CopyFields::new()
+        .exclude(["fee_cents"])
+        .rename("amount_cents", "total_cents");
+
+    let touched = Msg::<Touched>::copy(msg, &fields);
+    assert!(touched.is_err());
+
+    let msg = Msg::build(deposit()).build().unwrap();
+    let fields = CopyFields::new().include(["account_id", "amount_cents"]);
+
+    let touched = Msg::<Touched>::copy(msg, &fields).unwrap();
+    assert_eq!(
+        touched.data,
+        Touched {
+            account_id: String::from("123"),
+            amount_cents: 1100,
+        }
+    );
+}
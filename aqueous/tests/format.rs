@@ -0,0 +1,83 @@
+use aqueous::{
+    message::{Format, Json, Message, MessageData, Msg},
+    stream_name::StreamName,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Message, PartialEq, Serialize)]
+#[message(schema_version = 2)]
+struct Deposited {
+    account_id: String,
+    amount_cents: i64,
+    memo: Option<String>,
+    tags: Vec<String>,
+}
+
+fn msg() -> Msg<Deposited> {
+    let deposited = Deposited {
+        account_id: String::from("123"),
+        amount_cents: 1100,
+        memo: None,
+        tags: vec![String::from("payroll")],
+    };
+
+    Msg::build(deposited)
+        .correlation_stream_name(StreamName::new("transfer-1"))
+        .property("origin", "branch")
+        .build()
+        .unwrap()
+}
+
+fn round_trip<F: Format>() {
+    let message_data = MessageData::try_from(msg()).unwrap();
+    let msg = msg();
+
+    let metadata = message_data.encode_metadata::<F>().unwrap();
+    let data = message_data.encode_data::<F>().unwrap();
+    let decoded = MessageData::decode::<F>(message_data.id, "Deposited", &metadata, &data).unwrap();
+
+    assert_eq!(decoded.id, message_data.id);
+    assert_eq!(decoded.type_name, "Deposited");
+    assert_eq!(decoded.data, message_data.data);
+    assert_eq!(decoded.metadata.map, message_data.metadata.map);
+
+    let decoded = Msg::<Deposited>::from_data(decoded).unwrap();
+    assert_eq!(decoded.data, msg.data);
+
+    let (metadata, data) = msg.encode::<F>().unwrap();
+    let decoded = Msg::<Deposited>::decode::<F>(msg.id, &metadata, &data).unwrap();
+
+    assert_eq!(decoded.data, msg.data);
+    assert_eq!(decoded.metadata.schema_version().unwrap(), 2);
+    assert_eq!(
+        decoded.metadata.correlation_stream_name(),
+        Some(StreamName::new("transfer-1"))
+    );
+    assert_eq!(
+        decoded.metadata.get_property::<String>("origin").unwrap(),
+        "branch"
+    );
+}
+
+#[test]
+fn json_round_trips() {
+    round_trip::<Json>();
+
+    let (_, data) = msg().encode::<Json>().unwrap();
+    assert_eq!(
+        String::from_utf8(data).unwrap(),
+        r#"{"account_id":"123","amount_cents":1100,"memo":null,"tags":["payroll"]}"#
+    );
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn message_pack_round_trips() {
+    round_trip::<aqueous::message::MessagePack>();
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn cbor_round_trips() {
+    round_trip::<aqueous::message::Cbor>();
+}
@@ -0,0 +1,133 @@
+use aqueous::{
+    handler::{Handler, IntoHandler},
+    message::{Casing, Message, MessageData, MessageSet, Metadata, Msg, Upcasters},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Deserialize, Message, PartialEq, Serialize)]
+#[message(schema_version = 2)]
+struct Deposited {
+    account_id: String,
+    amount_cents: i64,
+}
+
+type Handled<M> = Arc<Mutex<Vec<M>>>;
+
+fn handler<M>(handled: &Handled<M>, casing: Casing, upcasters: Upcasters) -> impl Handler<(), ()>
+where
+    M: MessageSet + Send + 'static,
+{
+    let handled = handled.clone();
+
+    let handle = move |msg: Msg<M>| {
+        handled.lock().unwrap().push(msg.data);
+        async {}
+    };
+
+    IntoHandler::<(Msg<M>,), _, _>::into_handler(handle)
+        .casing(casing)
+        .upcasters(Arc::new(upcasters))
+}
+
+fn message_data(type_name: &str, schema_version: u32, data: serde_json::Value) -> MessageData {
+    MessageData {
+        id: Uuid::new_v4(),
+        type_name: type_name.to_owned(),
+        metadata: Metadata::new().set_schema_version(schema_version),
+        data,
+    }
+}
+
+#[tokio::test]
+async fn handlers_upcast_older_schema_versions() {
+    let handled = Handled::default();
+    let upcasters = Upcasters::new().register::<Deposited, _>(1, |mut data| {
+        data["amount_cents"] = json!(data["amount"].as_i64().unwrap() * 100);
+        data
+    });
+    let mut handler = handler::<Deposited>(&handled, Casing::Snake, upcasters);
+
+    let version_1 = json!({ "account_id": "123", "amount": 11 });
+    let version_2 = json!({ "account_id": "123", "amount_cents": 1200 });
+
+    assert!(
+        handler
+            .call(message_data("Deposited", 1, version_1), (), ())
+            .await
+    );
+    assert!(
+        handler
+            .call(message_data("Deposited", 2, version_2), (), ())
+            .await
+    );
+    assert!(
+        !handler
+            .call(message_data("Withdrawn", 2, json!({})), (), ())
+            .await
+    );
+
+    let amounts = handled
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|deposited| deposited.amount_cents)
+        .collect::<Vec<_>>();
+    assert_eq!(amounts, [1100, 1200]);
+}
+
+#[tokio::test]
+async fn handlers_decode_the_stored_casing() {
+    let handled = Handled::default();
+    let upcasters = Upcasters::new().register::<Deposited, _>(1, |mut data| {
+        data["amountCents"] = json!(data["amount"].as_i64().unwrap() * 100);
+        data
+    });
+    let mut handler = handler::<Deposited>(&handled, Casing::Camel, upcasters);
+
+    let mut version_1 = message_data("Deposited", 1, json!({ "accountId": "123", "amount": 11 }));
+    version_1.metadata = Casing::Camel.encode_metadata(version_1.metadata);
+    let mut version_2 = message_data(
+        "Deposited",
+        2,
+        json!({ "accountId": "123", "amountCents": 1200 }),
+    );
+    version_2.metadata = Casing::Camel.encode_metadata(version_2.metadata);
+
+    assert!(handler.call(version_1, (), ()).await);
+    assert!(handler.call(version_2, (), ()).await);
+
+    let handled = handled.lock().unwrap();
+    assert_eq!(handled[0].account_id, "123");
+    assert_eq!(handled[0].amount_cents, 1100);
+    assert_eq!(handled[1].amount_cents, 1200);
+}
+
+#[derive(Clone, Debug, Deserialize, Message, PartialEq, Serialize)]
+#[message(alias = "AccountOpened")]
+struct Opened {
+    account_id: String,
+}
+
+#[tokio::test]
+async fn handlers_accept_type_name_aliases() {
+    let handled = Handled::default();
+    let mut handler = handler::<Opened>(&handled, Casing::Snake, Upcasters::new());
+    let data = json!({ "account_id": "123" });
+
+    assert!(
+        handler
+            .call(message_data("Opened", 1, data.clone()), (), ())
+            .await
+    );
+    assert!(
+        handler
+            .call(message_data("AccountOpened", 1, data.clone()), (), ())
+            .await
+    );
+    assert!(!handler.call(message_data("Closed", 1, data), (), ()).await);
+
+    assert_eq!(handled.lock().unwrap().len(), 2);
+}
@@ -0,0 +1,240 @@
+mod common;
+
+use aqueous::{
+    idempotence::{Idempotent, ProcessedPositions, SequenceError, Sequenced},
+    message::{Message, MessageData, Msg, Replay},
+    stream_name::{Category, StreamName},
+};
+use common::InMemoryStore;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Message, Serialize)]
+struct Withdraw {
+    account_id: String,
+}
+
+#[derive(Debug, Deserialize, Message, Serialize)]
+struct Withdrawn {
+    account_id: String,
+}
+
+impl From<Withdraw> for Withdrawn {
+    fn from(withdraw: Withdraw) -> Self {
+        Self {
+            account_id: withdraw.account_id,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Account {
+    processed_positions: ProcessedPositions,
+}
+
+impl Idempotent for Account {
+    fn processed_positions(&self) -> &ProcessedPositions {
+        &self.processed_positions
+    }
+
+    fn processed_positions_mut(&mut self) -> &mut ProcessedPositions {
+        &mut self.processed_positions
+    }
+}
+
+/// A message as read from the message store
+fn read<T>(msg: Msg<T>, stream_name: &str, position: i64, global_position: i64) -> Msg<T> {
+    let metadata = msg
+        .metadata
+        .set_stream_name(StreamName::new(stream_name))
+        .set_position(position)
+        .set_global_position(global_position);
+
+    Msg { metadata, ..msg }
+}
+
+fn withdraw(global_position: i64) -> Msg<Withdraw> {
+    let withdraw = Withdraw {
+        account_id: String::from("123"),
+    };
+
+    let msg = Msg::build(withdraw).build().unwrap();
+
+    read(msg, "account:command-123", 3, global_position)
+}
+
+#[test]
+fn follow_records_the_causation_message() {
+    let withdrawn = Msg::<Withdrawn>::follow(withdraw(42));
+    let metadata = &withdrawn.metadata;
+
+    assert_eq!(
+        metadata.causation_message_stream_name(),
+        Some(StreamName::new("account:command-123"))
+    );
+    assert_eq!(metadata.causation_message_position(), Some(3));
+    assert_eq!(metadata.causation_message_global_position(), Some(42));
+
+    assert!(metadata.stream_name().is_none());
+    assert!(metadata.position().is_none());
+    assert!(metadata.global_position().is_none());
+}
+
+#[test]
+fn projected_events_record_their_causation_message_as_processed() {
+    let withdrawn = Msg::<Withdrawn>::follow(withdraw(42));
+
+    let withdrawn = read(withdrawn, "account-123", 0, 50);
+
+    let mut account = Account::default();
+    assert!(!account.is_processed(&withdraw(42)));
+
+    account.record_processed(&withdrawn);
+
+    let command_category = Category::new("account:command");
+    assert_eq!(
+        account.processed_positions().position(&command_category),
+        Some(42)
+    );
+    assert_eq!(
+        account
+            .processed_positions()
+            .position(&Category::new("account")),
+        None
+    );
+
+    assert!(account.is_processed(&withdraw(41)));
+    assert!(account.is_processed(&withdraw(42)));
+    assert!(!account.is_processed(&withdraw(43)));
+}
+
+#[test]
+fn processed_positions_serialize_by_category() {
+    let withdrawn = read(Msg::<Withdrawn>::follow(withdraw(42)), "account-123", 0, 50);
+    let mut account = Account::default();
+    account.record_processed(&withdrawn);
+
+    let json = serde_json::to_value(account.processed_positions()).unwrap();
+    assert_eq!(json, serde_json::json!({ "account:command": 42 }));
+
+    let processed_positions: ProcessedPositions = serde_json::from_value(json).unwrap();
+    assert_eq!(&processed_positions, account.processed_positions());
+}
+
+#[test]
+fn replayed_events_record_their_original_causation_message() {
+    let withdrawn = Msg::<Withdrawn>::follow(withdraw(42));
+    let withdrawn = read(withdrawn, "account-1", 0, 50);
+
+    let replay = Replay::new(StreamName::new("accountReplay-1"));
+    let message_data = MessageData::try_from(withdrawn).unwrap();
+    let message_data = MessageData {
+        metadata: message_data
+            .metadata
+            .set_stream_name(StreamName::new("account-1"))
+            .set_position(0)
+            .set_global_position(50),
+        ..message_data
+    };
+
+    let replayed = replay.message(message_data).unwrap();
+    let replayed = Msg::<Withdrawn>::from_data(replayed).unwrap();
+    let replayed = read(replayed, "accountReplay-1", 0, 60);
+
+    let mut account = Account::default();
+    account.record_processed(&replayed);
+
+    assert_eq!(
+        account
+            .processed_positions()
+            .position(&Category::new("account:command")),
+        Some(42)
+    );
+    assert_eq!(
+        account
+            .processed_positions()
+            .position(&Category::new("account")),
+        None
+    );
+}
+
+#[derive(Default)]
+struct SequencedAccount {
+    sequence: Option<i64>,
+}
+
+impl Sequenced for SequencedAccount {
+    fn sequence(&self) -> Option<i64> {
+        self.sequence
+    }
+}
+
+impl SequencedAccount {
+    fn apply(&mut self, withdrawn: &Msg<Withdrawn>) {
+        self.sequence = Self::event_sequence(withdrawn);
+    }
+}
+
+#[test]
+fn followed_sequence_is_processed_once_projected() {
+    let mut account = SequencedAccount::default();
+    assert!(!account.is_sequence_processed(&withdraw(42)));
+
+    let write = account
+        .follow_sequenced::<_, Withdrawn>(withdraw(42), None)
+        .unwrap();
+    let metadata = &write.msg.metadata;
+    assert_eq!(metadata.causation_message_global_position(), Some(42));
+    assert_eq!(metadata.causation_message_position(), Some(3));
+    assert_eq!(
+        metadata.causation_message_stream_name(),
+        Some(StreamName::new("account:command-123"))
+    );
+
+    let withdrawn = read(write.msg, "account-123", 0, 50);
+    account.apply(&withdrawn);
+
+    assert_eq!(account.sequence(), Some(42));
+    assert!(account.is_sequence_processed(&withdraw(42)));
+    assert!(!account.is_sequence_processed(&withdraw(43)));
+
+    let error = account
+        .follow_sequenced::<_, Withdrawn>(withdraw(42), Some(0))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        SequenceError::AlreadyProcessed {
+            sequence: 42,
+            entity_sequence: 42,
+        }
+    ));
+}
+
+#[tokio::test]
+async fn sequenced_writes_expect_the_projected_version() {
+    let store = InMemoryStore::default();
+    let stream_name = StreamName::new("account-123");
+    let account = SequencedAccount::default();
+
+    let write = account
+        .follow_sequenced::<_, Withdrawn>(withdraw(42), None)
+        .unwrap();
+    assert_eq!(write.write(&store, &stream_name).await.unwrap(), 0);
+
+    // Projected before the first write, so the stream has moved on
+    let write = account
+        .follow_sequenced::<_, Withdrawn>(withdraw(43), None)
+        .unwrap();
+    assert!(write.write(&store, &stream_name).await.is_err());
+
+    let write = account
+        .follow_sequenced::<_, Withdrawn>(withdraw(43), Some(0))
+        .unwrap();
+    assert_eq!(write.write(&store, &stream_name).await.unwrap(), 1);
+
+    let written = store.stream("account-123");
+    assert_eq!(written.len(), 2);
+    assert_eq!(
+        written[1].metadata.causation_message_global_position(),
+        Some(43)
+    );
+}
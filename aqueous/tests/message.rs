@@ -0,0 +1,402 @@
+mod common;
+
+use aqueous::{
+    message::{
+        Casing, Message, MessageData, MessageSet, Metadata, MetadataError, Msg, Replay,
+        ReplayError, UpcastError, Upcasters,
+    },
+    stream_name::StreamName,
+};
+use common::InMemoryStore;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use time::{Date, Month, PrimitiveDateTime, Time};
+use uuid::Uuid;
+
+fn written_at() -> PrimitiveDateTime {
+    let date = Date::from_calendar_date(2023, Month::February, 3).unwrap();
+    let time = Time::from_hms_micro(4, 5, 6, 123456).unwrap();
+
+    PrimitiveDateTime::new(date, time)
+}
+
+#[test]
+fn time_round_trips_through_serde() {
+    let metadata = Metadata::new().set_time(written_at());
+
+    let json = serde_json::to_string(&metadata).unwrap();
+    let metadata: Metadata = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(metadata.time(), Some(written_at()));
+}
+
+#[test]
+fn time_deserializes_from_array_form() {
+    let message_data: MessageData = serde_json::from_value(json!({
+        "id": Uuid::nil(),
+        "type_name": "Deposited",
+        "metadata": { "time": [2023, 34, 4, 5, 6, 123456000] },
+        "data": {},
+    }))
+    .unwrap();
+
+    assert_eq!(message_data.metadata.time(), Some(written_at()));
+}
+
+#[test]
+fn time_deserializes_from_iso_8601_form() {
+    let message_data: MessageData = serde_json::from_value(json!({
+        "id": Uuid::nil(),
+        "type_name": "Deposited",
+        "metadata": { "time": "2023-02-03T04:05:06.123456" },
+        "data": {},
+    }))
+    .unwrap();
+
+    assert_eq!(message_data.metadata.time(), Some(written_at()));
+}
+
+fn with_properties() -> Metadata {
+    Metadata::new()
+        .set_property("origin", "branch")
+        .set_local_property("trace_id", "abc")
+}
+
+#[test]
+fn local_properties_are_not_serialized() {
+    let metadata = with_properties();
+    assert_eq!(
+        metadata.get_local_property::<String>("trace_id").unwrap(),
+        "abc"
+    );
+
+    let json = serde_json::to_value(&metadata).unwrap();
+    assert_eq!(json, json!({ "properties": { "origin": "branch" } }));
+
+    let metadata: Metadata = serde_json::from_value(json).unwrap();
+    assert!(metadata.local_properties().is_empty());
+}
+
+#[test]
+fn following_keeps_properties_but_not_local_properties() {
+    let metadata = Metadata::follow(with_properties());
+
+    assert_eq!(metadata.get_property::<String>("origin").unwrap(), "branch");
+    assert!(metadata.local_properties().is_empty());
+}
+
+#[derive(Debug, Deserialize, Message, PartialEq, Serialize)]
+#[message(schema_version = 3)]
+struct Deposited {
+    account_id: String,
+    amount_cents: i64,
+}
+
+fn upcasters() -> Upcasters {
+    Upcasters::new()
+        .register::<Deposited, _>(1, |mut data| {
+            data["account_id"] = data["id"].take();
+            data
+        })
+        .register::<Deposited, _>(2, |mut data| {
+            data["amount_cents"] = json!(data["amount"].as_i64().unwrap() * 100);
+            data
+        })
+}
+
+fn written_as(schema_version: u32, data: serde_json::Value) -> MessageData {
+    MessageData {
+        id: Uuid::nil(),
+        type_name: String::from("Deposited"),
+        metadata: Metadata::new().set_schema_version(schema_version),
+        data,
+    }
+}
+
+#[test]
+fn upcast_applies_each_version_in_turn() {
+    let message_data = written_as(1, json!({ "id": "123", "amount": 11 }));
+
+    let message_data = upcasters().upcast::<Deposited>(message_data).unwrap();
+    assert_eq!(message_data.metadata.schema_version().unwrap(), 3);
+
+    let msg = Msg::<Deposited>::from_data(message_data).unwrap();
+    assert_eq!(
+        msg.data,
+        Deposited {
+            account_id: String::from("123"),
+            amount_cents: 1100,
+        }
+    );
+}
+
+#[test]
+fn upcast_leaves_current_version_untouched() {
+    let data = json!({ "account_id": "123", "amount_cents": 1100 });
+
+    let message_data = Upcasters::new()
+        .upcast::<Deposited>(written_as(3, data.clone()))
+        .unwrap();
+
+    assert_eq!(message_data.data, data);
+}
+
+#[test]
+fn upcast_requires_an_upcaster_per_version() {
+    let upcasters = Upcasters::new().register::<Deposited, _>(1, |data| data);
+
+    let error = upcasters
+        .upcast::<Deposited>(written_as(1, json!({})))
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        UpcastError::MissingUpcaster {
+            type_name: "Deposited",
+            schema_version: 2,
+        }
+    ));
+}
+
+#[test]
+fn upcast_rejects_newer_versions() {
+    let error = upcasters()
+        .upcast::<Deposited>(written_as(4, json!({})))
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        UpcastError::UnsupportedVersion {
+            type_name: "Deposited",
+            schema_version: 4,
+            supported_version: 3,
+        }
+    ));
+}
+
+#[test]
+fn missing_schema_versions_are_version_1() {
+    assert_eq!(Metadata::new().schema_version().unwrap(), 1);
+}
+
+#[test]
+fn malformed_schema_versions_are_rejected() {
+    for schema_version in [json!("2"), json!(0), json!(-1), json!(2.5), json!(null)] {
+        let metadata = Metadata::new().set(Metadata::SCHEMA_VERSION_KEY, schema_version.clone());
+
+        let error = metadata.schema_version().unwrap_err();
+        assert!(
+            matches!(error, MetadataError::InvalidSchemaVersion(value) if value == schema_version)
+        );
+
+        let message_data = MessageData {
+            metadata,
+            ..written_as(1, json!({}))
+        };
+        let error = upcasters().upcast::<Deposited>(message_data).unwrap_err();
+        assert!(matches!(error, UpcastError::Metadata(..)));
+    }
+}
+
+#[test]
+fn written_message_data_records_schema_version() {
+    let deposited = Deposited {
+        account_id: String::from("123"),
+        amount_cents: 1100,
+    };
+    let msg = Msg::build(deposited).build().unwrap();
+
+    let message_data = MessageData::try_from(msg).unwrap();
+
+    assert_eq!(message_data.metadata.schema_version().unwrap(), 3);
+    assert_eq!(
+        message_data
+            .metadata
+            .get_as::<u32>(Metadata::SCHEMA_VERSION_KEY),
+        Some(3)
+    );
+}
+
+#[test]
+fn written_message_data_keeps_its_causation() {
+    let deposited = Deposited {
+        account_id: String::from("123"),
+        amount_cents: 1100,
+    };
+    let msg = Msg::build(deposited).build().unwrap();
+
+    // As read from the message store
+    let metadata = msg
+        .metadata
+        .set_causation_message_stream_name(StreamName::new("account:command-1"))
+        .set_causation_message_global_position(42)
+        .set_stream_name(StreamName::new("account-1"))
+        .set_position(0)
+        .set_global_position(50);
+    let msg = Msg { metadata, ..msg };
+
+    let message_data = MessageData::try_from(msg).unwrap();
+    let metadata = &message_data.metadata;
+
+    assert_eq!(
+        metadata.causation_message_stream_name(),
+        Some(StreamName::new("account:command-1"))
+    );
+    assert_eq!(metadata.causation_message_global_position(), Some(42));
+    assert!(metadata.stream_name().is_none());
+    assert!(metadata.position().is_none());
+    assert!(metadata.global_position().is_none());
+}
+
+#[test]
+fn written_message_data_drops_local_properties() {
+    let deposited = Deposited {
+        account_id: String::from("123"),
+        amount_cents: 1100,
+    };
+    let msg = Msg::build(deposited).build().unwrap();
+    let msg = Msg {
+        metadata: with_properties(),
+        ..msg
+    };
+
+    let message_data = MessageData::try_from(msg).unwrap();
+
+    assert!(message_data.metadata.local_properties().is_empty());
+    assert_eq!(
+        message_data
+            .metadata
+            .get_property::<String>("origin")
+            .unwrap(),
+        "branch"
+    );
+}
+
+#[derive(Debug, Deserialize, Message, PartialEq, Serialize)]
+struct Withdrawn {
+    account_id: String,
+    amount_cents: i64,
+}
+
+#[derive(Debug, MessageSet)]
+enum Transaction {
+    Deposited(Deposited),
+    Withdrawn(Withdrawn),
+}
+
+#[test]
+fn message_and_message_set_methods_resolve_unambiguously() {
+    let deposited = Deposited {
+        account_id: String::from("123"),
+        amount_cents: 1100,
+    };
+
+    assert_eq!(deposited.type_name(), "Deposited");
+    assert!(Deposited::is_type_name("Deposited"));
+    assert_eq!(deposited.message_type_name(), "Deposited");
+    assert!(Deposited::accepts_type_name("Deposited"));
+
+    assert!(Transaction::accepts_type_name("Withdrawn"));
+    assert!(!Transaction::accepts_type_name("Opened"));
+
+    let message_data = written_as(1, json!({ "account_id": "123", "amount_cents": 1100 }));
+    let message_data = MessageData {
+        type_name: String::from("Withdrawn"),
+        ..message_data
+    };
+
+    let msg = Transaction::msg_from_data(message_data, Casing::Snake, &Upcasters::new()).unwrap();
+    assert_eq!(msg.data.message_type_name(), "Withdrawn");
+}
+
+#[test]
+fn replay_records_the_original_stream() {
+    let replay = Replay::new(StreamName::new("accountReplay-123"));
+
+    let mut message_data = written_as(3, json!({}));
+    message_data.metadata = message_data
+        .metadata
+        .set_stream_name(StreamName::new("account-123"));
+
+    let replayed = replay.message(message_data).unwrap();
+    assert_eq!(
+        replayed.metadata.replay_stream_name(),
+        Some(StreamName::new("account-123"))
+    );
+    assert!(replayed.metadata.stream_name().is_none());
+    assert!(replayed.metadata.causation_message_stream_name().is_none());
+
+    // Replaying a replay keeps the original stream
+    let replayed = replay
+        .message(MessageData {
+            metadata: replayed
+                .metadata
+                .set_stream_name(StreamName::new("accountReplay-123")),
+            ..replayed
+        })
+        .unwrap();
+    assert_eq!(
+        replayed.metadata.replay_stream_name(),
+        Some(StreamName::new("account-123"))
+    );
+}
+
+#[test]
+fn replay_requires_a_source_stream() {
+    let replay = Replay::new(StreamName::new("accountReplay-123"));
+
+    let error = replay.message(written_as(3, json!({}))).unwrap_err();
+
+    assert!(matches!(error, ReplayError::MissingStreamName(id) if id == Uuid::nil()));
+}
+
+#[tokio::test]
+async fn replay_writes_the_source_into_the_replay_stream() {
+    let store = InMemoryStore::default()
+        .write("account-123", "Deposited")
+        .write("account-456", "Deposited")
+        .write("account-123", "Withdrawn");
+    let replay = Replay::new(StreamName::new("accountReplay-123"));
+
+    let count = replay
+        .write(&store, StreamName::new("account-123"))
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let source = store.stream("account-123");
+    let replayed = store.stream("accountReplay-123");
+
+    let type_names = replayed
+        .iter()
+        .map(|message_data| message_data.type_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(type_names, ["Deposited", "Withdrawn"]);
+
+    for (original, replayed) in source.iter().zip(&replayed) {
+        assert_ne!(replayed.id, original.id);
+        assert_eq!(
+            replayed.metadata.replay_stream_name(),
+            Some(StreamName::new("account-123"))
+        );
+    }
+}
+
+#[test]
+fn messages_following_a_replay_are_not_replays() {
+    let replay = Replay::new(StreamName::new("accountReplay-123"));
+
+    let mut message_data = written_as(3, json!({ "account_id": "123", "amount_cents": 1 }));
+    message_data.metadata = message_data
+        .metadata
+        .set_stream_name(StreamName::new("account-123"));
+
+    let replayed = replay.message(message_data).unwrap();
+    assert!(replayed.metadata.is_replay());
+
+    let replayed = Msg::<Deposited>::from_data(replayed).unwrap();
+    let followed = Msg::<Deposited>::follow(replayed);
+
+    assert!(!followed.metadata.is_replay());
+    assert!(followed.metadata.replay_stream_name().is_none());
+}
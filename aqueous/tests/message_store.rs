@@ -0,0 +1,62 @@
+mod common;
+
+use aqueous::{message_store::MessageStore, stream_name::StreamName};
+use common::InMemoryStore;
+
+#[tokio::test]
+async fn empty_streams_have_no_version_or_last_message() {
+    let store = InMemoryStore::default().write_many("account-456", 2);
+    let stream_name = StreamName::new("account-123");
+
+    assert_eq!(store.stream_version(&stream_name).await.unwrap(), None);
+    assert!(store
+        .get_last_stream_message(&stream_name, None)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn stream_version_is_the_last_message_position() {
+    let store = InMemoryStore::default()
+        .write_many("account-123", 3)
+        .write_many("account-456", 5);
+
+    let version = store
+        .stream_version(&StreamName::new("account-123"))
+        .await
+        .unwrap();
+    assert_eq!(version, Some(2));
+}
+
+#[tokio::test]
+async fn last_stream_message_can_be_of_a_type() {
+    let store = InMemoryStore::default()
+        .write("account-123", "Snapshot")
+        .write("account-123", "Deposited")
+        .write("account-123", "Snapshot")
+        .write("account-123", "Withdrawn")
+        .write("account-456", "Snapshot");
+    let stream_name = StreamName::new("account-123");
+
+    let last = store
+        .get_last_stream_message(&stream_name, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(last.type_name, "Withdrawn");
+    assert_eq!(last.metadata.position(), Some(3));
+
+    let snapshot = store
+        .get_last_stream_message(&stream_name, Some("Snapshot"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snapshot.metadata.position(), Some(2));
+
+    let opened = store
+        .get_last_stream_message(&stream_name, Some("Opened"))
+        .await
+        .unwrap();
+    assert!(opened.is_none());
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use aqueous::{
+    message::{MessageData, Metadata},
+    message_store::{CategoryFilter, MessageStore, Reader, ReaderError},
+    stream_name::StreamName,
+};
+use common::InMemoryStore;
+use futures::TryStreamExt;
+use std::pin::pin;
+
+async fn positions<S>(reader: Reader<'_, S>) -> Vec<i64>
+where
+    S: MessageStore + Sync,
+{
+    let messages: Vec<MessageData> = reader.read().try_collect().await.unwrap();
+
+    messages
+        .iter()
+        .map(|message_data| message_data.metadata.position().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn reads_streams_of_an_exact_multiple_of_the_batch_size() {
+    let store = InMemoryStore::default().write_many("account-123", 6);
+    let reader = Reader::new(&store, StreamName::new("account-123")).batch_size(3);
+
+    assert_eq!(positions(reader).await, [0, 1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn reads_a_partial_last_batch() {
+    let store = InMemoryStore::default()
+        .write_many("account-123", 7)
+        .write_many("account-456", 2);
+    let reader = Reader::new(&store, StreamName::new("account-123")).batch_size(3);
+
+    assert_eq!(positions(reader).await, [0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[tokio::test]
+async fn reads_from_a_stream_position() {
+    let store = InMemoryStore::default().write_many("account-123", 5);
+    let reader = Reader::new(&store, StreamName::new("account-123"))
+        .position(2)
+        .batch_size(2);
+
+    assert_eq!(positions(reader).await, [2, 3, 4]);
+}
+
+#[tokio::test]
+async fn reads_empty_streams() {
+    let store = InMemoryStore::default().write_many("account-456", 2);
+    let reader = Reader::new(&store, StreamName::new("account-123")).batch_size(2);
+
+    assert!(positions(reader).await.is_empty());
+}
+
+#[tokio::test]
+async fn reads_categories_from_a_global_position() {
+    let store = InMemoryStore::default()
+        .write("account-123", "Deposited")
+        .write("account-456", "Deposited")
+        .write("transfer-1", "Initiated")
+        .write("account-123", "Withdrawn")
+        .write("account:command-123", "Withdraw")
+        .write("account-456", "Withdrawn")
+        .write("account-789", "Deposited");
+
+    let reader = Reader::new(&store, StreamName::new("account"))
+        .position(3)
+        .batch_size(2);
+    let messages: Vec<MessageData> = reader.read().try_collect().await.unwrap();
+
+    let global_positions = messages
+        .iter()
+        .map(|message_data| message_data.metadata.global_position().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(global_positions, [4, 6, 7]);
+
+    let reader = Reader::new(&store, StreamName::new("account"))
+        .batch_size(2)
+        .filter(CategoryFilter::new().type_names(["Withdrawn"]));
+    let messages: Vec<MessageData> = reader.read().try_collect().await.unwrap();
+
+    let global_positions = messages
+        .iter()
+        .map(|message_data| message_data.metadata.global_position().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(global_positions, [4, 6]);
+}
+
+#[tokio::test]
+async fn full_batches_without_a_position_fail_the_read() {
+    let store = InMemoryStore::default().write_many("account-123", 3);
+    let id = {
+        let mut messages = store.messages.lock().unwrap();
+        messages[1].metadata.map.remove(Metadata::POSITION_KEY);
+        messages[1].id
+    };
+
+    // The message without a position ends the first batch
+    let reader = Reader::new(&store, StreamName::new("account-123")).batch_size(2);
+    let mut messages = pin!(reader.read());
+
+    let first = messages.try_next().await.unwrap().unwrap();
+    assert_eq!(first.metadata.position(), Some(0));
+
+    let second = messages.try_next().await.unwrap().unwrap();
+    assert_eq!(second.id, id);
+
+    let error = messages.try_next().await.unwrap_err();
+    assert!(matches!(error, ReaderError::MissingPosition(missing) if missing == id));
+}
@@ -0,0 +1,296 @@
+use aqueous::stream_name::{
+    hash_64, Category, CategoryPattern, CategoryType, EntityID, GroupHashing, StreamID, StreamName,
+    StreamNameError,
+};
+use proptest::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+use std::{collections::HashMap, fmt::Debug};
+use uuid::Uuid;
+
+proptest! {
+    #[test]
+    fn from_parts_and_split_are_inverses(
+        category in "[a-z][a-zA-Z0-9]*(:[a-z]+(\\+[a-z]+)*)?",
+        id in "[a-zA-Z0-9][a-zA-Z0-9-]*(\\+[a-zA-Z0-9-]+)*",
+    ) {
+        let category = Category::new(category);
+        let id = StreamID::new(id);
+
+        let stream_name = StreamName::from_parts(category.clone(), id.clone());
+
+        prop_assert_eq!(stream_name.split(), (category, Some(id)));
+    }
+
+    #[test]
+    fn views_match_owned_accessors(stream_name in "[a-z]+(:[a-z]+(\\+[a-z]+)*)?(-[a-z0-9-]+(\\+[a-z0-9-]+)*)?") {
+        let stream_name = StreamName::new(stream_name);
+        let view = stream_name.view();
+
+        prop_assert_eq!(view.category().to_category(), stream_name.category());
+        prop_assert_eq!(view.id().map(StreamID::new), stream_name.id());
+        prop_assert_eq!(view.cardinal_id().map(StreamID::new), stream_name.cardinal_id());
+
+        let category = stream_name.category();
+        let types = view.category().category_types().map(CategoryType::new).collect::<Vec<_>>();
+        prop_assert_eq!(types, category.category_types());
+    }
+
+    #[test]
+    fn uuid_ids_round_trip(bytes in any::<[u8; 16]>()) {
+        let uuid = Uuid::from_bytes(bytes);
+        let stream_name = StreamName::from_parts(Category::new("account"), StreamID::from(uuid));
+
+        prop_assert_eq!(stream_name.id().and_then(|id| id.as_uuid()), Some(uuid));
+        prop_assert_eq!(stream_name.cardinal_id().and_then(|id| id.as_uuid()), Some(uuid));
+    }
+
+    #[test]
+    fn compound_ids_round_trip(account in any::<[u8; 16]>(), transaction in any::<u64>()) {
+        let compound_id = (Uuid::from_bytes(account), transaction);
+        let stream_name = StreamName::from_parts(
+            Category::new("transfer"),
+            StreamID::from_compound(&compound_id),
+        );
+
+        prop_assert_eq!(stream_name.compound_id(), Some(Ok(compound_id)));
+    }
+}
+
+#[test]
+fn parse_accepts_valid_stream_names() {
+    let stream_names = [
+        "account",
+        "account-123",
+        "account:command",
+        "account:command-123",
+        "account:command+position-123",
+        "account-123+456",
+        "account:snapshot-123-456",
+        "account-00000000-0000-0000-0000-000000000000",
+    ];
+
+    for stream_name in stream_names {
+        assert_eq!(
+            StreamName::parse(stream_name),
+            Ok(StreamName::new(stream_name)),
+            "{stream_name}"
+        );
+    }
+}
+
+#[test]
+fn parse_rejects_invalid_stream_names() {
+    use StreamNameError::*;
+
+    let invalid = |stream_name: &str| stream_name.to_owned();
+
+    let cases = [
+        ("", Empty),
+        (" ", InvalidCharacter(invalid(" "))),
+        ("account -1", InvalidCharacter(invalid("account -1"))),
+        ("account-1\n", InvalidCharacter(invalid("account-1\n"))),
+        (
+            "account\u{7}-1",
+            InvalidCharacter(invalid("account\u{7}-1")),
+        ),
+        ("-1", EmptyCategory(invalid("-1"))),
+        (":command-1", EmptyCategory(invalid(":command-1"))),
+        ("account:-1", EmptyCategoryType(invalid("account:-1"))),
+        ("account:", EmptyCategoryType(invalid("account:"))),
+        (
+            "account:command+",
+            EmptyCategoryType(invalid("account:command+")),
+        ),
+        (
+            "account::command",
+            ExtraCategoryTypeSeparator(invalid("account::command")),
+        ),
+        (
+            "account:command:x-1",
+            ExtraCategoryTypeSeparator(invalid("account:command:x-1")),
+        ),
+        ("account-", EmptyId(invalid("account-"))),
+        ("account-1+", EmptyId(invalid("account-1+"))),
+        ("account-+1", EmptyId(invalid("account-+1"))),
+    ];
+
+    for (stream_name, error) in cases {
+        assert_eq!(
+            StreamName::parse(stream_name),
+            Err(error),
+            "{stream_name:?}"
+        );
+    }
+}
+
+#[test]
+fn hash_64_matches_message_db() {
+    // select hash_64('abc'), the first 16 hex digits of md5('abc') as a bigint
+    assert_eq!(hash_64("abc"), -8070080442485551184);
+    assert_eq!(hash_64("123"), 2318431741638412123);
+}
+
+#[test]
+fn consumer_group_member_is_the_absolute_hash_modulo_group_size() {
+    let stream_name = StreamName::new("account-abc");
+
+    assert_eq!(
+        stream_name.consumer_group_member(3, GroupHashing::CardinalId),
+        Some(2)
+    );
+    assert_eq!(
+        stream_name.consumer_group_member(1, GroupHashing::CardinalId),
+        Some(0)
+    );
+
+    let stream_name = StreamName::new("account-abc+123");
+
+    assert_eq!(
+        stream_name.consumer_group_member(7, GroupHashing::CardinalId),
+        Some(2)
+    );
+    assert_eq!(
+        stream_name.consumer_group_member(7, GroupHashing::CompoundId),
+        Some(hash_64("abc+123").unsigned_abs() as i64 % 7)
+    );
+}
+
+#[test]
+fn consumer_group_member_requires_a_positive_group_size() {
+    let stream_name = StreamName::new("account-abc");
+
+    assert_eq!(
+        stream_name.consumer_group_member(0, GroupHashing::CardinalId),
+        None
+    );
+    assert_eq!(
+        stream_name.consumer_group_member(-3, GroupHashing::CardinalId),
+        None
+    );
+    assert_eq!(
+        StreamName::new("account").consumer_group_member(3, GroupHashing::CardinalId),
+        None
+    );
+}
+
+fn assert_plain_string<T>(value: T, string: &str)
+where
+    T: Debug + DeserializeOwned + PartialEq + Serialize,
+{
+    assert_eq!(serde_json::to_value(&value).unwrap(), json!(string));
+    assert_eq!(serde_json::from_value::<T>(json!(string)).unwrap(), value);
+}
+
+#[test]
+fn stream_name_parts_serialize_as_plain_strings() {
+    assert_plain_string(
+        StreamName::new("account:command-123"),
+        "account:command-123",
+    );
+    assert_plain_string(Category::new("account:command"), "account:command");
+    assert_plain_string(EntityID::new("account"), "account");
+    assert_plain_string(CategoryType::new("command+position"), "command+position");
+    assert_plain_string(StreamID::new("123+456"), "123+456");
+}
+
+#[test]
+fn categories_serialize_as_map_keys() {
+    let positions = HashMap::from([(Category::new("account:command"), 42)]);
+
+    let json = serde_json::to_value(&positions).unwrap();
+    assert_eq!(json, json!({ "account:command": 42 }));
+
+    let positions: HashMap<Category, i64> = serde_json::from_value(json).unwrap();
+    assert_eq!(positions[&Category::new("account:command")], 42);
+}
+
+fn glob(pattern: &str, category: &str) -> bool {
+    CategoryPattern::new(pattern).matches(&Category::new(category))
+}
+
+#[test]
+fn patterns_without_wildcards_match_exactly() {
+    assert!(glob("account", "account"));
+    assert!(!glob("account", "accounts"));
+    assert!(!glob("account", "accoun"));
+    assert!(!glob("", "account"));
+    assert!(glob("", ""));
+}
+
+#[test]
+fn star_matches_any_run_of_characters() {
+    assert!(glob("*", ""));
+    assert!(glob("*", "account:command"));
+    assert!(glob("account*", "account"));
+    assert!(glob("account*", "account:command"));
+    assert!(glob("*:command", "account:command"));
+    assert!(!glob("*:command", "account:position"));
+    assert!(glob("a**t", "account"));
+}
+
+#[test]
+fn question_mark_matches_a_single_character() {
+    assert!(glob("accoun?", "account"));
+    assert!(!glob("accoun?", "accoun"));
+    assert!(!glob("accoun?", "accounts"));
+    assert!(glob("?*", "a"));
+    assert!(!glob("?*", ""));
+}
+
+#[test]
+fn star_backtracks_to_later_matches() {
+    // The first `:` is not the one the rest of the pattern matches
+    assert!(glob("*:command", "account:position:command"));
+    assert!(glob("a*b*c", "aXbYbZc"));
+    assert!(glob("*ab", "aab"));
+    assert!(!glob("a*b*c", "aXbYbZ"));
+    assert!(glob("*?t", "account"));
+}
+
+#[test]
+fn patterns_match_the_category_of_stream_names() {
+    let pattern = CategoryPattern::new("account*");
+
+    assert!(pattern.matches_stream_name(&StreamName::new("account-123")));
+    assert!(pattern.matches_stream_name(&StreamName::new("account:command-123")));
+    assert!(!pattern.matches_stream_name(&StreamName::new("transfer-account")));
+}
+
+#[test]
+fn category_predicates() {
+    let command = Category::new("account:command+position");
+    let entity = Category::new("account");
+
+    assert!(command.has_types());
+    assert!(!entity.has_types());
+
+    assert!(command.has_type(&CategoryType::new("command")));
+    assert!(command.has_type(&CategoryType::new("position")));
+    assert!(!command.has_type(&CategoryType::new("snapshot")));
+    assert!(!entity.has_type(&CategoryType::new("command")));
+
+    assert!(command.is_command());
+    assert!(Category::new_command("account").is_command());
+    assert!(!entity.is_command());
+    assert!(!Category::new_position("account").is_command());
+}
+
+#[test]
+fn categories_match_their_streams() {
+    let category = Category::new("account");
+
+    assert!(category.matches(&StreamName::new("account")));
+    assert!(category.matches(&StreamName::new("account-123")));
+    assert!(category.matches(&StreamName::new("account-123+456")));
+    assert!(!category.matches(&StreamName::new("account:command-123")));
+    assert!(!category.matches(&StreamName::new("accounts-123")));
+}
+
+#[test]
+fn stream_names_without_ids_are_categories() {
+    assert!(StreamName::new("account").is_category());
+    assert!(StreamName::new("account:command").is_category());
+    assert!(!StreamName::new("account-123").is_category());
+    assert!(!StreamName::new("account:command-123").is_category());
+}
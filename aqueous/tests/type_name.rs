@@ -0,0 +1,83 @@
+use aqueous::message::Message;
+
+#[derive(Message)]
+struct FundsDeposited;
+
+#[derive(Message)]
+#[message(type_name = "Deposited")]
+struct DepositRecorded;
+
+#[derive(Message)]
+#[message(type_name = "Deposited", rename_all = "snake_case")]
+struct DepositNamed;
+
+macro_rules! renamed {
+    ($($ident:ident => $rule:literal),* $(,)?) => {
+        $(
+            #[derive(Message)]
+            #[message(rename_all = $rule)]
+            struct $ident;
+        )*
+    };
+}
+
+renamed! {
+    LowerFundsDeposited => "lowercase",
+    UpperFundsDeposited => "UPPERCASE",
+    PascalFundsDeposited => "PascalCase",
+    CamelFundsDeposited => "camelCase",
+    SnakeFundsDeposited => "snake_case",
+    ScreamingSnakeFundsDeposited => "SCREAMING_SNAKE_CASE",
+    KebabFundsDeposited => "kebab-case",
+    ScreamingKebabFundsDeposited => "SCREAMING-KEBAB-CASE",
+}
+
+#[test]
+fn type_name_is_the_ident_by_default() {
+    assert_eq!(FundsDeposited::TYPE_NAME, "FundsDeposited");
+    assert_eq!(FundsDeposited.type_name(), "FundsDeposited");
+}
+
+#[test]
+fn type_name_attribute_overrides_the_ident_and_rename_all() {
+    assert_eq!(DepositRecorded::TYPE_NAME, "Deposited");
+    assert_eq!(DepositNamed::TYPE_NAME, "Deposited");
+}
+
+#[test]
+fn rename_all_rules_apply_to_the_ident() {
+    assert_eq!(LowerFundsDeposited::TYPE_NAME, "lowerfundsdeposited");
+    assert_eq!(UpperFundsDeposited::TYPE_NAME, "UPPERFUNDSDEPOSITED");
+    assert_eq!(PascalFundsDeposited::TYPE_NAME, "PascalFundsDeposited");
+    assert_eq!(CamelFundsDeposited::TYPE_NAME, "camelFundsDeposited");
+    assert_eq!(SnakeFundsDeposited::TYPE_NAME, "snake_funds_deposited");
+    assert_eq!(
+        ScreamingSnakeFundsDeposited::TYPE_NAME,
+        "SCREAMING_SNAKE_FUNDS_DEPOSITED"
+    );
+    assert_eq!(KebabFundsDeposited::TYPE_NAME, "kebab-funds-deposited");
+    assert_eq!(
+        ScreamingKebabFundsDeposited::TYPE_NAME,
+        "SCREAMING-KEBAB-FUNDS-DEPOSITED"
+    );
+}
+
+#[derive(Message)]
+#[message(alias = "Deposit", alias = "AccountDeposited")]
+struct Deposited;
+
+#[test]
+fn aliases_are_accepted_type_names() {
+    assert_eq!(Deposited::TYPE_NAME, "Deposited");
+    assert_eq!(
+        Deposited::TYPE_NAME_ALIASES,
+        ["Deposit", "AccountDeposited"]
+    );
+
+    assert!(Deposited::is_type_name("Deposited"));
+    assert!(Deposited::is_type_name("Deposit"));
+    assert!(Deposited::is_type_name("AccountDeposited"));
+    assert!(!Deposited::is_type_name("Withdrawn"));
+
+    assert!(!FundsDeposited::is_type_name("Deposit"));
+}
@@ -0,0 +1,115 @@
+use aqueous::{
+    message_store::{listen_sql, notification_channel, Notifications, Wakeup, MAX_CHANNEL_LENGTH},
+    stream_name::Category,
+};
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::{sync::mpsc, time::Instant};
+
+// Timers fire on the millisecond after they elapse, so elapsed time is compared in seconds
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+struct Channel(mpsc::UnboundedReceiver<i64>);
+
+#[async_trait]
+impl Notifications for Channel {
+    async fn notified(&mut self) -> Option<i64> {
+        self.0.recv().await
+    }
+}
+
+fn wakeup() -> (mpsc::UnboundedSender<i64>, Wakeup<Channel>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    (sender, Wakeup::new(Channel(receiver), POLL_INTERVAL))
+}
+
+#[tokio::test]
+async fn notifications_wake_before_the_poll_interval() {
+    tokio::time::pause();
+    let (sender, mut wakeup) = wakeup();
+
+    let notify = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        sender.send(11).unwrap();
+        sender
+    });
+
+    let start = Instant::now();
+    wakeup.wait().await;
+
+    assert_eq!(start.elapsed().as_secs(), 1);
+    assert!(wakeup.is_listening());
+
+    notify.await.unwrap();
+}
+
+#[tokio::test]
+async fn missed_notifications_wake_after_the_poll_interval() {
+    tokio::time::pause();
+    let (_sender, mut wakeup) = wakeup();
+
+    let start = Instant::now();
+    wakeup.wait().await;
+
+    assert_eq!(start.elapsed().as_secs(), POLL_INTERVAL.as_secs());
+    assert!(wakeup.is_listening());
+}
+
+#[tokio::test]
+async fn closed_notifications_fall_back_to_polling() {
+    tokio::time::pause();
+    let (sender, mut wakeup) = wakeup();
+    drop(sender);
+
+    let start = Instant::now();
+    wakeup.wait().await;
+
+    assert_eq!(start.elapsed(), Duration::ZERO);
+    assert!(!wakeup.is_listening());
+
+    wakeup.wait().await;
+
+    assert_eq!(start.elapsed().as_secs(), POLL_INTERVAL.as_secs());
+}
+
+#[test]
+fn channels_are_named_for_their_category() {
+    let category = Category::new("account:command");
+
+    assert_eq!(
+        notification_channel(&category),
+        "message_store:account:command"
+    );
+    assert_eq!(
+        listen_sql(&category),
+        r#"LISTEN "message_store:account:command""#
+    );
+}
+
+#[test]
+fn listened_channels_are_quoted() {
+    let category = Category::new(r#"someAccount"s"#);
+
+    assert_eq!(
+        listen_sql(&category),
+        r#"LISTEN "message_store:someAccount""s""#
+    );
+}
+
+#[test]
+fn long_channels_use_the_category_digest() {
+    let category = Category::new("a".repeat(50));
+    let channel = notification_channel(&category);
+
+    // select md5(repeat('a', 50))
+    let digest = format!("{:x}", md5::compute("a".repeat(50)));
+    assert_eq!(channel, format!("message_store:{digest}"));
+    assert!(channel.len() <= MAX_CHANNEL_LENGTH);
+
+    let category = Category::new("a".repeat(49));
+    let channel = notification_channel(&category);
+
+    assert_eq!(channel, format!("message_store:{}", "a".repeat(49)));
+    assert_eq!(channel.len(), MAX_CHANNEL_LENGTH);
+}
@@ -1 +1,3 @@
-fn main() {}
+use aqueous::stream_name::category;
+
+category!(pub Account, "account");